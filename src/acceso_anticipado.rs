@@ -0,0 +1,111 @@
+//! Este módulo contiene la comparación de las reseñas de cada juego escritas durante su
+//! acceso anticipado y después de su lanzamiento (columna `written_during_early_access`).
+
+// Imports de crates externas.
+use serde::Serialize;
+
+// Imports de funciones/estructuras propias.
+use crate::calificacion::ConteoRecomendaciones;
+use crate::estadisticas::{ReseñaDestacada, Votos};
+
+/// Reseñas de un juego escritas en una de sus fases (acceso anticipado o lanzamiento).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct ReseñasFase {
+    pub cantidad: u64,
+    pub recomendaciones: ConteoRecomendaciones,
+    /// Reseñas que se cuentan con votos útiles (ver `PoliticaVotosDesbordados`).
+    pub con_votos: u64,
+    pub suma_votos: u128,
+    /// Mejor reseña de la fase, según la métrica de las mejores reseñas.
+    pub mejor: Option<ReseñaDestacada>,
+}
+
+/// Reseñas de un juego separadas según si se escribieron durante su acceso anticipado.
+///
+/// Las reseñas sin valor en `written_during_early_access` no se cuentan en ninguna fase.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct FasesLanzamiento {
+    pub acceso_anticipado: ReseñasFase,
+    pub lanzamiento: ReseñasFase,
+}
+
+impl ReseñasFase {
+    /// Promedio de votos útiles de las reseñas con votos, si hay alguna.
+    pub fn promedio_votos(&self) -> Option<f64> {
+        (self.con_votos > 0).then(|| self.suma_votos as f64 / self.con_votos as f64)
+    }
+
+    /// Registra una reseña de la fase.
+    ///
+    /// # Argumentos
+    /// * `recomendada` - Si la reseña recomienda el juego (`recommended`).
+    /// * `votos_utiles` - Votos útiles de la reseña (`None` si se cuenta sin votos).
+    /// * `candidata` - La reseña con su valor en la métrica de las mejores reseñas, si
+    ///   compite por ser la mejor. Su texto se copia sólo si lo es.
+    fn registrar(
+        &mut self,
+        recomendada: Option<bool>,
+        votos_utiles: Option<Votos>,
+        candidata: Option<ReseñaDestacada<&str>>,
+    ) {
+        self.cantidad += 1;
+        self.recomendaciones.registrar(recomendada);
+        if let Some(votos) = votos_utiles {
+            self.con_votos += 1;
+            self.suma_votos += u128::from(votos);
+        }
+
+        if let Some(candidata) = candidata
+            && self.mejor.as_ref().is_none_or(|mejor| candidata < *mejor)
+        {
+            self.mejor = Some(candidata.con_texto_propio());
+        }
+    }
+
+    /// Fusiona las reseñas de `self` en `destino`.
+    fn merge_into(self, destino: &mut ReseñasFase) {
+        destino.cantidad += self.cantidad;
+        destino.recomendaciones.sumar(&self.recomendaciones);
+        destino.con_votos += self.con_votos;
+        destino.suma_votos += self.suma_votos;
+        if let Some(mejor) = self.mejor
+            && destino.mejor.as_ref().is_none_or(|actual| mejor < *actual)
+        {
+            destino.mejor = Some(mejor);
+        }
+    }
+}
+
+impl FasesLanzamiento {
+    /// Registra una reseña en la fase en la que se escribió.
+    ///
+    /// # Argumentos
+    /// * `acceso_anticipado` - Si se escribió durante el acceso anticipado
+    ///   (`written_during_early_access`). Si es `None`, la reseña no se cuenta.
+    /// * `recomendada` - Si la reseña recomienda el juego (`recommended`).
+    /// * `votos_utiles` - Votos útiles de la reseña (`None` si se cuenta sin votos).
+    /// * `candidata` - La reseña con su valor en la métrica de las mejores reseñas, si
+    ///   compite por ser la mejor de la fase.
+    pub fn registrar(
+        &mut self,
+        acceso_anticipado: Option<bool>,
+        recomendada: Option<bool>,
+        votos_utiles: Option<Votos>,
+        candidata: Option<ReseñaDestacada<&str>>,
+    ) {
+        let fase = match acceso_anticipado {
+            Some(true) => &mut self.acceso_anticipado,
+            Some(false) => &mut self.lanzamiento,
+            None => return,
+        };
+
+        fase.registrar(recomendada, votos_utiles, candidata);
+    }
+
+    /// Fusiona las fases de `self` en `destino`.
+    pub fn merge_into(self, destino: &mut FasesLanzamiento) {
+        self.acceso_anticipado
+            .merge_into(&mut destino.acceso_anticipado);
+        self.lanzamiento.merge_into(&mut destino.lanzamiento);
+    }
+}
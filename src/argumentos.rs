@@ -3,6 +3,16 @@
 // Imports de crates externas.
 use std::env;
 
+// Imports de funciones/estructuras propias.
+use crate::calificacion::OpcionesCalificacion;
+use crate::estadisticas_serializables::OpcionesSalida;
+use crate::hyperloglog::{PRECISION_MAXIMA, PRECISION_MINIMA};
+use crate::limites::{Limite, LimitesIndicados, LimitesRanking};
+use crate::linea_de_tiempo::Periodo;
+use crate::metricas::MetricaReseñas;
+use crate::procesadores::{OpcionesProcesamiento, PoliticaVotosDesbordados};
+use crate::segmentador::TamañoChunk;
+
 // Constantes.
 const CANTIDAD_ARGUMENTOS_ESPERADA: usize = 4;
 const POS_RUTA: usize = 1;
@@ -10,10 +20,78 @@ const POS_CANT_HILOS: usize = 2;
 const POS_NOM_SALIDA: usize = 3;
 const EXTENSION_ARCHIVO_SALIDA: &str = ".json";
 
+// Opciones.
+const OPCION_CHUNKS_EN_VUELO: &str = "--chunks-en-vuelo";
+const OPCION_FILAS_POR_CHUNK: &str = "--filas-por-chunk";
+const OPCION_BYTES_POR_CHUNK: &str = "--bytes-por-chunk";
+const OPCION_REPORTE_INGESTA: &str = "--reporte-ingesta";
+const OPCION_ESTRICTO: &str = "--estricto";
+const OPCION_ENTRADA: &str = "--entrada";
+const OPCION_RECURSIVO: &str = "--recursivo";
+const OPCION_INCLUIR: &str = "--incluir";
+const OPCION_EXCLUIR: &str = "--excluir";
+const OPCION_VOTOS_DESBORDADOS: &str = "--votos-desbordados";
+const OPCION_DEDUPLICAR: &str = "--deduplicar";
+const OPCION_CONFIGURACION: &str = "--configuracion";
+const OPCION_TOP_JUEGOS: &str = "--top-juegos";
+const OPCION_IDIOMAS_POR_JUEGO: &str = "--idiomas-por-juego";
+const OPCION_TOP_IDIOMAS: &str = "--top-idiomas";
+const OPCION_REVIEWS_POR_IDIOMA: &str = "--reviews-por-idioma";
+const OPCION_TOP_CALIFICADOS: &str = "--top-calificados";
+const OPCION_CONFIANZA: &str = "--confianza";
+const OPCION_MINIMO_RECOMENDACIONES: &str = "--minimo-recomendaciones";
+const OPCION_LINEA_DE_TIEMPO: &str = "--linea-de-tiempo";
+const OPCION_PRECISION_AUTORES: &str = "--precision-autores";
+const OPCION_METRICA_REVIEWS: &str = "--metrica-reviews";
+
 // Mensajes.
 const ERROR_USO_INCORRECTO: &str = "⚠️ Uso incorrecto.";
-const EXPLICACION_DE_EJECUCION: &str =
-    "👉 Ejecutá el programa como: cargo run <ruta-archivo> <cantidad-threads> <archivo-salida>";
+const EXPLICACION_DE_EJECUCION: &str = "👉 Ejecutá el programa como: cargo run <ruta-entrada> <cantidad-threads> <archivo-salida> [opciones]";
+const EXPLICACION_OPCIONES: &str = "👉 Opciones disponibles:
+    --chunks-en-vuelo <n>    Máximo de chunks leídos y todavía no procesados (por defecto, 2 por hilo).
+    --filas-por-chunk <n>    Tamaño de cada chunk en reseñas (por defecto, 100000).
+    --bytes-por-chunk <n>    Tamaño aproximado de cada chunk en bytes.
+    --reporte-ingesta        Incluye en el .json el reporte de filas aceptadas y rechazadas.
+    --estricto               Aborta ante la primera fila malformada o archivo ilegible.
+    --entrada <ruta>         Agrega otro archivo o directorio a procesar (se puede repetir, '-' es la entrada estándar).
+    --recursivo              Recorre también los subdirectorios de los directorios de entrada.
+    --incluir <patrón>       Procesa sólo los archivos de los directorios que cumplan el patrón 'glob'.
+    --excluir <patrón>       Descarta los archivos de los directorios que cumplan el patrón 'glob'.
+    --votos-desbordados <p>  Qué hacer con los votos que no entran en un u32: descartar (por defecto),
+                             ignorar-votos, saturar o ampliar.
+    --deduplicar             Cuenta una sola vez las reseñas con el mismo review_id (la actualizada más recientemente).
+    --top-juegos <n|all>     Cantidad de juegos del ranking (por defecto, 3).
+    --idiomas-por-juego <n|all>
+                             Cantidad de idiomas de cada juego del ranking (por defecto, 3).
+    --top-idiomas <n|all>    Cantidad de idiomas del ranking (por defecto, 3).
+    --reviews-por-idioma <n|all>
+                             Cantidad de reseñas de cada idioma del ranking (por defecto, 10).
+    --top-calificados <n|all>
+                             Cantidad de juegos del ranking por calificación (por defecto, 3).
+    --configuracion <ruta>   Archivo .json con los tamaños de los rankings (las opciones anteriores tienen prioridad).
+    --confianza <c>          Nivel de confianza del ranking por calificación y del sesgo de las copias
+                             gratuitas, entre 0 y 1 (por defecto, 0.95).
+    --minimo-recomendaciones <n>
+                             Mínimo de reseñas con recomendación para entrar al ranking por
+                             calificación y de cada grupo comparado en el sesgo de las copias
+                             gratuitas (por defecto, 50).
+    --linea-de-tiempo <p>    Incluye en el .json las reseñas de cada juego e idioma por período: dia,
+                             semana o mes.
+    --precision-autores <p>  Precisión (de 4 a 16) de la estimación de autores distintos: usa 2^p bytes
+                             por juego e idioma, con un error relativo de 1.04/√(2^p) (por defecto, 12).
+    --metrica-reviews <m>    Métrica con la que se eligen las mejores reseñas: utiles (por defecto),
+                             graciosos, puntaje o comentarios.";
+const ERROR_OPCION_DESCONOCIDA: &str = "❌ Opción desconocida:";
+const ERROR_OPCION_SIN_VALOR: &str = "❌ Falta el valor de la opción";
+const ERROR_POLITICA_VOTOS: &str = "❌ Política de votos desbordados desconocida:";
+const ERROR_PERIODO: &str = "❌ Período de la línea de tiempo desconocido:";
+const ERROR_METRICA_REVIEWS: &str = "❌ Métrica de reseñas desconocida:";
+const ERROR_PRECISION_AUTORES: &str = "❌ La precisión de autores debe ser un número entero entre";
+const ERROR_VALOR_OPCION: &str = "❌ El valor de la opción debe ser un número entero positivo:";
+const ERROR_LIMITE_OPCION: &str = "❌ Valor inválido de la opción";
+const ERROR_CONFIANZA_OPCION: &str =
+    "❌ El nivel de confianza debe ser un número mayor a 0 y menor a 1:";
+const ERROR_ARCHIVO_CONFIGURACION: &str = "❌ No se pudo leer el archivo de configuración";
 const ERROR_TIPO_CANT_HILOS: &str = "❌ El valor de hilos debe ser un número entero positivo.";
 const ERROR_CANTIDAD_HILOS: &str = "❌ Demasiados hilos solicitados: pediste";
 const EXPLICACION_HILOS_1: &str = "Tu máquina tiene";
@@ -26,24 +104,260 @@ const MULTIPLICADOR_CANT_HILOS: usize = 10;
 
 /// Estructura auxiliar para guardar la configuracion seleccionada al ejecutar el programa.
 pub struct Configuracion {
-    pub ruta_archivo: String,
+    pub rutas_entrada: Vec<String>,
     pub cantidad_threads: usize,
     pub nombre_archivo_salida: String,
+    pub procesamiento: OpcionesProcesamiento,
+    pub salida: OpcionesSalida,
 }
 
 /// Funcion que valida la cantidad de argumentos recibidos por consola.  
 /// Devuelve los argumentos en formato 'Vector de Strings'.
 fn obtener_argumentos() -> Option<Vec<String>> {
     let args: Vec<String> = env::args().collect();
-    if args.len() != CANTIDAD_ARGUMENTOS_ESPERADA {
+    if args.len() < CANTIDAD_ARGUMENTOS_ESPERADA {
         eprintln!("{}", ERROR_USO_INCORRECTO);
         eprintln!("{}", EXPLICACION_DE_EJECUCION);
+        eprintln!("{}", EXPLICACION_OPCIONES);
         return None;
     }
 
     Some(args)
 }
 
+/// Funcion que valida que una opción tenga valor.  
+/// Devuelve el valor de la opción.
+fn validar_valor(opcion: &str, valor: Option<&String>) -> Option<String> {
+    if valor.is_none() {
+        eprintln!("{} '{}'.", ERROR_OPCION_SIN_VALOR, opcion);
+    }
+
+    valor.cloned()
+}
+
+/// Funcion que valida el valor de la opción `--votos-desbordados`.  
+/// Devuelve la política de votos desbordados indicada.
+fn validar_politica_votos(
+    opcion: &str,
+    valor: Option<&String>,
+) -> Option<PoliticaVotosDesbordados> {
+    let valor = validar_valor(opcion, valor)?;
+    let politica = PoliticaVotosDesbordados::desde_opcion(&valor);
+    if politica.is_none() {
+        eprintln!("{} '{}'.", ERROR_POLITICA_VOTOS, valor);
+    }
+
+    politica
+}
+
+/// Funcion que valida el valor de la opción `--linea-de-tiempo`.  
+/// Devuelve la duración de los períodos indicada.
+fn validar_periodo(opcion: &str, valor: Option<&String>) -> Option<Periodo> {
+    let valor = validar_valor(opcion, valor)?;
+    let periodo = Periodo::desde_opcion(&valor);
+    if periodo.is_none() {
+        eprintln!("{} '{}'.", ERROR_PERIODO, valor);
+    }
+
+    periodo
+}
+
+/// Funcion que valida el valor de la opción `--metrica-reviews`.  
+/// Devuelve la métrica de las mejores reseñas indicada.
+fn validar_metrica_reviews(opcion: &str, valor: Option<&String>) -> Option<MetricaReseñas> {
+    let valor = validar_valor(opcion, valor)?;
+    let metrica = MetricaReseñas::desde_opcion(&valor);
+    if metrica.is_none() {
+        eprintln!("{} '{}'.", ERROR_METRICA_REVIEWS, valor);
+    }
+
+    metrica
+}
+
+/// Funcion que valida el valor de la opción `--precision-autores`.  
+/// Devuelve la precisión indicada (entre `PRECISION_MINIMA` y `PRECISION_MAXIMA`).
+fn validar_precision_autores(opcion: &str, valor: Option<&String>) -> Option<u8> {
+    let valor = validar_valor(opcion, valor)?;
+    match valor.parse::<u8>() {
+        Ok(precision) if (PRECISION_MINIMA..=PRECISION_MAXIMA).contains(&precision) => {
+            Some(precision)
+        }
+        _ => {
+            eprintln!(
+                "{} {} y {}: '{}'.",
+                ERROR_PRECISION_AUTORES, PRECISION_MINIMA, PRECISION_MAXIMA, valor
+            );
+            None
+        }
+    }
+}
+
+/// Funcion que valida el valor numérico de una opción.  
+/// Devuelve el valor como un entero positivo.
+fn validar_valor_positivo(opcion: &str, valor: Option<&String>) -> Option<usize> {
+    let Some(valor) = valor else {
+        eprintln!("{} '{}'.", ERROR_OPCION_SIN_VALOR, opcion);
+        return None;
+    };
+
+    match valor.parse::<usize>() {
+        Ok(numero) if numero > 0 => Some(numero),
+        _ => {
+            eprintln!("{} {} '{}'.", ERROR_VALOR_OPCION, opcion, valor);
+            None
+        }
+    }
+}
+
+/// Funcion que valida el valor de una opción de tamaño de ranking.  
+/// Devuelve el límite indicado (un número entero positivo o `all`).
+fn validar_limite(opcion: &str, valor: Option<&String>) -> Option<Limite> {
+    let valor = validar_valor(opcion, valor)?;
+    match Limite::desde_texto(&valor) {
+        Ok(limite) => Some(limite),
+        Err(e) => {
+            eprintln!("{} {}: {}.", ERROR_LIMITE_OPCION, opcion, e);
+            None
+        }
+    }
+}
+
+/// Funcion que valida el valor de la opción `--confianza`.  
+/// Devuelve el nivel de confianza indicado (mayor a 0 y menor a 1).
+fn validar_confianza(opcion: &str, valor: Option<&String>) -> Option<f64> {
+    let valor = validar_valor(opcion, valor)?;
+    match valor.parse::<f64>() {
+        Ok(confianza) if OpcionesCalificacion::es_confianza_valida(confianza) => Some(confianza),
+        _ => {
+            eprintln!("{} '{}'.", ERROR_CONFIANZA_OPCION, valor);
+            None
+        }
+    }
+}
+
+/// Funcion que lee el archivo de configuración indicado con `--configuracion`.  
+/// Devuelve los tamaños de ranking definidos en el archivo.
+fn validar_configuracion(opcion: &str, valor: Option<&String>) -> Option<LimitesIndicados> {
+    let ruta = validar_valor(opcion, valor)?;
+    match LimitesIndicados::desde_archivo(&ruta) {
+        Ok(limites) => Some(limites),
+        Err(e) => {
+            eprintln!("{} '{}': {}.", ERROR_ARCHIVO_CONFIGURACION, ruta, e);
+            None
+        }
+    }
+}
+
+/// Funcion que parsea las opciones que siguen a los argumentos obligatorios.  
+/// Agrega a `rutas_entrada` las rutas indicadas con `--entrada`.  
+/// Los tamaños de ranking indicados por consola tienen prioridad sobre los del archivo de
+/// configuración, sin importar el orden de las opciones.  
+/// Devuelve las opciones de procesamiento y de salida con los valores indicados por consola.
+fn parsear_opciones(
+    opciones: &[String],
+    rutas_entrada: &mut Vec<String>,
+) -> Option<(OpcionesProcesamiento, OpcionesSalida)> {
+    let mut procesamiento = OpcionesProcesamiento::default();
+    let mut salida = OpcionesSalida::default();
+    let mut limites_configuracion = LimitesIndicados::default();
+    let mut limites_consola = LimitesIndicados::default();
+    let mut iter = opciones.iter();
+    while let Some(opcion) = iter.next() {
+        match opcion.as_str() {
+            OPCION_CHUNKS_EN_VUELO => {
+                procesamiento.max_chunks_en_vuelo =
+                    Some(validar_valor_positivo(opcion, iter.next())?);
+            }
+
+            OPCION_FILAS_POR_CHUNK => {
+                procesamiento.tamaño_chunk =
+                    TamañoChunk::Filas(validar_valor_positivo(opcion, iter.next())?);
+            }
+
+            OPCION_BYTES_POR_CHUNK => {
+                procesamiento.tamaño_chunk =
+                    TamañoChunk::Bytes(validar_valor_positivo(opcion, iter.next())?);
+            }
+
+            OPCION_REPORTE_INGESTA => salida.incluir_reporte_ingesta = true,
+            OPCION_ESTRICTO => procesamiento.estricto = true,
+            OPCION_ENTRADA => rutas_entrada.push(validar_valor(opcion, iter.next())?),
+            OPCION_RECURSIVO => procesamiento.entrada.recursivo = true,
+            OPCION_DEDUPLICAR => procesamiento.deduplicar = true,
+            OPCION_INCLUIR => procesamiento
+                .entrada
+                .incluir
+                .push(validar_valor(opcion, iter.next())?),
+
+            OPCION_EXCLUIR => procesamiento
+                .entrada
+                .excluir
+                .push(validar_valor(opcion, iter.next())?),
+
+            OPCION_VOTOS_DESBORDADOS => {
+                procesamiento.votos_desbordados = validar_politica_votos(opcion, iter.next())?;
+            }
+
+            OPCION_LINEA_DE_TIEMPO => {
+                procesamiento.linea_de_tiempo = Some(validar_periodo(opcion, iter.next())?);
+            }
+
+            OPCION_PRECISION_AUTORES => {
+                procesamiento.precision_autores = validar_precision_autores(opcion, iter.next())?;
+            }
+
+            OPCION_METRICA_REVIEWS => {
+                procesamiento.metrica_reviews = validar_metrica_reviews(opcion, iter.next())?;
+            }
+
+            OPCION_CONFIGURACION => {
+                limites_configuracion = validar_configuracion(opcion, iter.next())?;
+            }
+
+            OPCION_TOP_JUEGOS => {
+                limites_consola.top_juegos = Some(validar_limite(opcion, iter.next())?);
+            }
+
+            OPCION_IDIOMAS_POR_JUEGO => {
+                limites_consola.idiomas_por_juego = Some(validar_limite(opcion, iter.next())?);
+            }
+
+            OPCION_TOP_IDIOMAS => {
+                limites_consola.top_idiomas = Some(validar_limite(opcion, iter.next())?);
+            }
+
+            OPCION_REVIEWS_POR_IDIOMA => {
+                limites_consola.reviews_por_idioma = Some(validar_limite(opcion, iter.next())?);
+            }
+
+            OPCION_TOP_CALIFICADOS => {
+                limites_consola.top_calificados = Some(validar_limite(opcion, iter.next())?);
+            }
+
+            OPCION_CONFIANZA => {
+                salida.calificacion.confianza = validar_confianza(opcion, iter.next())?;
+            }
+
+            OPCION_MINIMO_RECOMENDACIONES => {
+                salida.calificacion.minimo_reseñas =
+                    validar_valor_positivo(opcion, iter.next())? as u64;
+            }
+
+            _ => {
+                eprintln!("{} '{}'.", ERROR_OPCION_DESCONOCIDA, opcion);
+                eprintln!("{}", EXPLICACION_OPCIONES);
+                return None;
+            }
+        }
+    }
+
+    let mut limites = LimitesRanking::default();
+    limites_configuracion.aplicar(&mut limites);
+    limites_consola.aplicar(&mut limites);
+    procesamiento.limites = limites;
+    Some((procesamiento, salida))
+}
+
 /// Funcion que valida la cantidad de hilos ingresados por consola.  
 /// Devuelve la cantidad de hilos ingresada y validada.
 fn validar_cantidad_hilos(hilos_str: &str) -> Option<usize> {
@@ -80,16 +394,20 @@ fn validar_cantidad_hilos(hilos_str: &str) -> Option<usize> {
 /// Devuelve la 'Configuracion' segun los argumentos recibidos en la ejecución del programa.
 pub fn parsear_argumentos() -> Option<Configuracion> {
     let args = obtener_argumentos()?;
-    let ruta_archivo = args[POS_RUTA].clone();
+    let mut rutas_entrada = vec![args[POS_RUTA].clone()];
     let cantidad_threads = validar_cantidad_hilos(&args[POS_CANT_HILOS])?;
     let mut nombre_archivo_salida = args[POS_NOM_SALIDA].clone();
     if !nombre_archivo_salida.ends_with(EXTENSION_ARCHIVO_SALIDA) {
         nombre_archivo_salida.push_str(EXTENSION_ARCHIVO_SALIDA);
     }
 
+    let (procesamiento, salida) =
+        parsear_opciones(&args[CANTIDAD_ARGUMENTOS_ESPERADA..], &mut rutas_entrada)?;
     Some(Configuracion {
-        ruta_archivo,
+        rutas_entrada,
         cantidad_threads,
         nombre_archivo_salida,
+        procesamiento,
+        salida,
     })
 }
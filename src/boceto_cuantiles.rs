@@ -0,0 +1,115 @@
+//! Este módulo contiene el boceto de cuantiles usado para resumir distribuciones de
+//! valores (como el tiempo de juego) sin tener que almacenar todos los valores.
+
+// Imports de crates externas.
+use std::collections::BTreeMap;
+
+// Constantes.
+/// Error relativo máximo de los cuantiles estimados (1%).
+const PRECISION_RELATIVA: f64 = 0.01;
+
+/// Boceto de cuantiles de valores enteros no negativos, al estilo de 'DDSketch'.
+///
+/// Los valores positivos se cuentan en cubetas de tamaño logarítmico: la cubeta `i`
+/// contiene los valores en `(γ^(i-1), γ^i]`, con `γ = (1 + α) / (1 - α)`, por lo que
+/// cualquier cuantil se estima con un error relativo menor a `α` (`PRECISION_RELATIVA`).
+/// La cantidad de cubetas crece con el logaritmo del rango de los valores y no con su
+/// cantidad, y la fusión de dos bocetos suma sus cubetas: es exacta e independiente del
+/// orden, igual que la de los conteos. La cantidad, la suma, el mínimo y el máximo se
+/// guardan de forma exacta.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BocetoCuantiles {
+    cantidad: u64,
+    suma: u128,
+    minimo: Option<u64>,
+    maximo: Option<u64>,
+    ceros: u64,
+    cubetas: BTreeMap<i32, u64>,
+}
+
+impl BocetoCuantiles {
+    /// Registra un valor.
+    pub fn registrar(&mut self, valor: u64) {
+        self.cantidad += 1;
+        self.suma += u128::from(valor);
+        self.minimo = Some(self.minimo.map_or(valor, |minimo| minimo.min(valor)));
+        self.maximo = Some(self.maximo.map_or(valor, |maximo| maximo.max(valor)));
+        if valor == 0 {
+            self.ceros += 1;
+        } else {
+            *self.cubetas.entry(indice_cubeta(valor)).or_insert(0) += 1;
+        }
+    }
+
+    /// Cantidad de valores registrados.
+    pub fn cantidad(&self) -> u64 {
+        self.cantidad
+    }
+
+    /// Promedio exacto de los valores, si hay alguno.
+    pub fn promedio(&self) -> Option<f64> {
+        (self.cantidad > 0).then(|| self.suma as f64 / self.cantidad as f64)
+    }
+
+    /// Estimación del cuantil `q` (de 0 a 1) de los valores, si hay alguno.
+    ///
+    /// El cuantil es el valor de posición `⌊q · (n - 1)⌋` de los valores ordenados; el 0 y
+    /// el 1 devuelven el mínimo y el máximo exactos.
+    pub fn cuantil(&self, q: f64) -> Option<f64> {
+        let (minimo, maximo) = (self.minimo?, self.maximo?);
+        let posicion = (q.clamp(0.0, 1.0) * (self.cantidad - 1) as f64).floor() as u64;
+        if posicion == 0 {
+            return Some(minimo as f64);
+        }
+        if posicion == self.cantidad - 1 {
+            return Some(maximo as f64);
+        }
+
+        let mut acumulado = self.ceros;
+        if acumulado > posicion {
+            return Some(0.0);
+        }
+
+        for (indice, cantidad) in &self.cubetas {
+            acumulado += cantidad;
+            if acumulado > posicion {
+                return Some(valor_cubeta(*indice).clamp(minimo as f64, maximo as f64));
+            }
+        }
+
+        Some(maximo as f64)
+    }
+
+    /// Fusiona los valores de `self` en `destino`.
+    pub fn merge_into(self, destino: &mut BocetoCuantiles) {
+        destino.cantidad += self.cantidad;
+        destino.suma += self.suma;
+        destino.minimo = match (destino.minimo, self.minimo) {
+            (Some(propio), Some(otro)) => Some(propio.min(otro)),
+            (propio, otro) => propio.or(otro),
+        };
+        // `None` es menor que cualquier `Some`, por lo que el máximo no necesita el caso
+        // especial del mínimo.
+        destino.maximo = destino.maximo.max(self.maximo);
+        destino.ceros += self.ceros;
+        for (indice, cantidad) in self.cubetas {
+            *destino.cubetas.entry(indice).or_insert(0) += cantidad;
+        }
+    }
+}
+
+/// Base `γ` de las cubetas logarítmicas.
+fn gamma() -> f64 {
+    (1.0 + PRECISION_RELATIVA) / (1.0 - PRECISION_RELATIVA)
+}
+
+/// Índice de la cubeta de un valor positivo: el menor `i` tal que `valor <= γ^i`.
+fn indice_cubeta(valor: u64) -> i32 {
+    ((valor as f64).ln() / gamma().ln()).ceil() as i32
+}
+
+/// Valor representativo de una cubeta, a menos de `α` de cualquier valor de la cubeta.
+fn valor_cubeta(indice: i32) -> f64 {
+    let gamma = gamma();
+    2.0 * gamma.powi(indice) / (gamma + 1.0)
+}
@@ -0,0 +1,302 @@
+//! Este módulo contiene el conteo de recomendaciones (columna `recommended`), la
+//! calificación resumida con la que Steam las muestra y el puntaje con el que se ordena
+//! el ranking de juegos mejor calificados.
+
+// Imports de crates externas.
+use serde::Serialize;
+
+// Constantes.
+const MINIMO_RESEÑAS_CALIFICACION: u64 = 10;
+const MINIMO_RESEÑAS_VOLUMEN_MEDIO: u64 = 50;
+const MINIMO_RESEÑAS_VOLUMEN_ALTO: u64 = 500;
+const PORCENTAJE_ABRUMADORAMENTE_POSITIVO: f64 = 95.0;
+const PORCENTAJE_POSITIVO: f64 = 80.0;
+const PORCENTAJE_MAYORMENTE_POSITIVO: f64 = 70.0;
+const PORCENTAJE_VARIADO: f64 = 40.0;
+const PORCENTAJE_MAYORMENTE_NEGATIVO: f64 = 20.0;
+const CONFIANZA_POR_DEFECTO: f64 = 0.95;
+const MINIMO_RESEÑAS_RANKING: u64 = 50;
+
+// Coeficientes de la aproximación de Acklam de la inversa de la distribución normal
+// estándar (con un error relativo menor a 1.15e-9).
+const COEFICIENTES_A: [f64; 6] = [
+    -3.969_683_028_665_376e1,
+    2.209_460_984_245_205e2,
+    -2.759_285_104_469_687e2,
+    1.383_577_518_672_69e2,
+    -3.066_479_806_614_716e1,
+    2.506_628_277_459_239,
+];
+const COEFICIENTES_B: [f64; 6] = [
+    -5.447_609_879_822_406e1,
+    1.615_858_368_580_409e2,
+    -1.556_989_798_598_866e2,
+    6.680_131_188_771_972e1,
+    -1.328_068_155_288_572e1,
+    1.0,
+];
+const COEFICIENTES_C: [f64; 6] = [
+    -7.784_894_002_430_293e-3,
+    -3.223_964_580_411_365e-1,
+    -2.400_758_277_161_838,
+    -2.549_732_539_343_734,
+    4.374_664_141_464_968,
+    2.938_163_982_698_783,
+];
+const COEFICIENTES_D: [f64; 5] = [
+    7.784_695_709_041_462e-3,
+    3.224_671_290_700_398e-1,
+    2.445_134_137_142_996,
+    3.754_408_661_907_416,
+    1.0,
+];
+const PROBABILIDAD_COLA: f64 = 0.02425;
+
+// Coeficientes de la aproximación de Lanczos (con g = 7) del logaritmo de la función
+// gamma (con un error relativo menor a 1e-15).
+const LANCZOS_G: f64 = 7.0;
+const COEFICIENTES_LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Cantidad de reseñas que recomiendan y que no recomiendan un juego.
+///
+/// Las reseñas sin valor en la columna `recommended` no se cuentan.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ConteoRecomendaciones {
+    pub positivas: u64,
+    pub negativas: u64,
+}
+
+/// Calificación resumida de Steam según el porcentaje de reseñas positivas y la
+/// cantidad de reseñas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalificacionSteam {
+    AbrumadoramentePositiva,
+    MuyPositiva,
+    Positiva,
+    MayormentePositiva,
+    Variada,
+    MayormenteNegativa,
+    Negativa,
+    MuyNegativa,
+    AbrumadoramenteNegativa,
+}
+
+/// Parámetros de las decisiones estadísticas sobre las recomendaciones: el ranking de
+/// juegos mejor calificados (`top_rated_games`) y el sesgo de las copias gratuitas
+/// (`purchase_channels`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpcionesCalificacion {
+    /// Nivel de confianza del límite inferior de Wilson y del sesgo de las copias
+    /// gratuitas (entre 0 y 1, sin incluirlos).
+    pub confianza: f64,
+    /// Cantidad mínima de reseñas con recomendación para entrar al ranking, y de cada
+    /// grupo comparado en el sesgo de las copias gratuitas.
+    pub minimo_reseñas: u64,
+}
+
+impl ConteoRecomendaciones {
+    /// Registra el valor de `recommended` de una reseña.
+    pub fn registrar(&mut self, recomendada: Option<bool>) {
+        match recomendada {
+            Some(true) => self.positivas += 1,
+            Some(false) => self.negativas += 1,
+            None => {}
+        }
+    }
+
+    /// Suma las recomendaciones de `otras` a las de `self`.
+    pub fn sumar(&mut self, otras: &ConteoRecomendaciones) {
+        self.positivas += otras.positivas;
+        self.negativas += otras.negativas;
+    }
+
+    /// Cantidad de reseñas con valor en `recommended`.
+    pub fn total(&self) -> u64 {
+        self.positivas + self.negativas
+    }
+
+    /// Porcentaje (de 0 a 100) de reseñas positivas, si hay alguna reseña contada.
+    pub fn porcentaje_positivo(&self) -> Option<f64> {
+        let total = self.total();
+        (total > 0).then(|| self.positivas as f64 * 100.0 / total as f64)
+    }
+
+    /// Calificación de Steam, si hay suficientes reseñas para calcularla.
+    ///
+    /// Se usan los umbrales de Steam: con menos de 10 reseñas no hay calificación; los
+    /// extremos (positivos y negativos) dependen además del volumen de reseñas (10, 50 y
+    /// 500 reseñas).
+    pub fn calificacion(&self) -> Option<CalificacionSteam> {
+        let total = self.total();
+        if total < MINIMO_RESEÑAS_CALIFICACION {
+            return None;
+        }
+
+        let porcentaje = self.porcentaje_positivo()?;
+        let calificacion = if porcentaje >= PORCENTAJE_POSITIVO {
+            if total >= MINIMO_RESEÑAS_VOLUMEN_ALTO
+                && porcentaje >= PORCENTAJE_ABRUMADORAMENTE_POSITIVO
+            {
+                CalificacionSteam::AbrumadoramentePositiva
+            } else if total >= MINIMO_RESEÑAS_VOLUMEN_MEDIO {
+                CalificacionSteam::MuyPositiva
+            } else {
+                CalificacionSteam::Positiva
+            }
+        } else if porcentaje >= PORCENTAJE_MAYORMENTE_POSITIVO {
+            CalificacionSteam::MayormentePositiva
+        } else if porcentaje >= PORCENTAJE_VARIADO {
+            CalificacionSteam::Variada
+        } else if porcentaje >= PORCENTAJE_MAYORMENTE_NEGATIVO {
+            CalificacionSteam::MayormenteNegativa
+        } else if total >= MINIMO_RESEÑAS_VOLUMEN_ALTO {
+            CalificacionSteam::AbrumadoramenteNegativa
+        } else if total >= MINIMO_RESEÑAS_VOLUMEN_MEDIO {
+            CalificacionSteam::MuyNegativa
+        } else {
+            CalificacionSteam::Negativa
+        };
+
+        Some(calificacion)
+    }
+
+    /// Límite inferior del intervalo de Wilson de la proporción (de 0 a 1) de reseñas
+    /// positivas, para el valor `z` de la normal estándar del nivel de confianza.
+    ///
+    /// A diferencia del porcentaje, penaliza a los juegos con pocas reseñas: un juego
+    /// con 5 reseñas positivas de 5 queda por debajo de uno con 950 de 1000.
+    pub fn limite_inferior_wilson(&self, z: f64) -> Option<f64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+
+        let total = total as f64;
+        let proporcion = self.positivas as f64 / total;
+        let z2 = z * z;
+        let centro = proporcion + z2 / (2.0 * total);
+        let margen =
+            z * (proporcion * (1.0 - proporcion) / total + z2 / (4.0 * total * total)).sqrt();
+        Some((centro - margen) / (1.0 + z2 / total))
+    }
+
+    /// Valor p de la prueba exacta de Fisher (unilateral) de que las reseñas de `self`
+    /// son más positivas que las de `otras`: la probabilidad de que, repartiendo al azar
+    /// las reseñas positivas de ambas, `self` tenga, al menos, sus reseñas positivas.
+    ///
+    /// A diferencia de la aproximación normal, es exacta aun con pocas reseñas o con
+    /// proporciones extremas (todas positivas o todas negativas).
+    pub fn valor_p_mas_positivas(&self, otras: &ConteoRecomendaciones) -> f64 {
+        let total = self.total() + otras.total();
+        let positivas = self.positivas + otras.positivas;
+        let (muestra, maximo) = (self.total(), positivas.min(self.total()));
+
+        // Probabilidad hipergeométrica de que `self` tenga exactamente `self.positivas`
+        // reseñas positivas, y de ahí en adelante con la razón entre términos sucesivos.
+        let mut termino = (ln_combinaciones(positivas, self.positivas)
+            + ln_combinaciones(total - positivas, muestra - self.positivas)
+            - ln_combinaciones(total, muestra))
+        .exp();
+        let mut valor_p = 0.0;
+        for k in self.positivas..=maximo {
+            valor_p += termino;
+            if k < maximo {
+                termino *= ((positivas - k) * (muestra - k)) as f64
+                    / ((k + 1) * (total - positivas + k + 1 - muestra)) as f64;
+            }
+        }
+
+        valor_p.min(1.0)
+    }
+}
+
+impl OpcionesCalificacion {
+    /// Indica si `confianza` es un nivel de confianza válido (entre 0 y 1, sin incluirlos).
+    pub fn es_confianza_valida(confianza: f64) -> bool {
+        confianza > 0.0 && confianza < 1.0
+    }
+
+    /// Valor `z` de la normal estándar correspondiente al nivel de confianza (por
+    /// ejemplo, 1.96 para 0.95).
+    pub fn valor_z(&self) -> f64 {
+        cuantil_normal((1.0 + self.confianza) / 2.0)
+    }
+}
+
+impl Default for OpcionesCalificacion {
+    fn default() -> Self {
+        OpcionesCalificacion {
+            confianza: CONFIANZA_POR_DEFECTO,
+            minimo_reseñas: MINIMO_RESEÑAS_RANKING,
+        }
+    }
+}
+
+impl CalificacionSteam {
+    /// Nombre de la calificación, tal como la muestra Steam.
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            CalificacionSteam::AbrumadoramentePositiva => "Overwhelmingly Positive",
+            CalificacionSteam::MuyPositiva => "Very Positive",
+            CalificacionSteam::Positiva => "Positive",
+            CalificacionSteam::MayormentePositiva => "Mostly Positive",
+            CalificacionSteam::Variada => "Mixed",
+            CalificacionSteam::MayormenteNegativa => "Mostly Negative",
+            CalificacionSteam::Negativa => "Negative",
+            CalificacionSteam::MuyNegativa => "Very Negative",
+            CalificacionSteam::AbrumadoramenteNegativa => "Overwhelmingly Negative",
+        }
+    }
+}
+
+/// Evalúa un polinomio (con los coeficientes del término de mayor grado al de menor
+/// grado) con el método de Horner.
+fn evaluar_polinomio(coeficientes: &[f64], x: f64) -> f64 {
+    coeficientes
+        .iter()
+        .fold(0.0, |acumulado, coeficiente| acumulado * x + coeficiente)
+}
+
+/// Logaritmo de la función gamma en `x` (mayor o igual a 1), con la aproximación de
+/// Lanczos.
+fn ln_gamma(x: f64) -> f64 {
+    let x = x - 1.0;
+    let suma = COEFICIENTES_LANCZOS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFICIENTES_LANCZOS[0], |suma, (i, coeficiente)| {
+            suma + coeficiente / (x + i as f64 + 1.0)
+        });
+    let t = x + LANCZOS_G + 0.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + suma.ln()
+}
+
+/// Logaritmo de la cantidad de combinaciones de `k` elementos de `n` (con `k <= n`).
+fn ln_combinaciones(n: u64, k: u64) -> f64 {
+    ln_gamma(n as f64 + 1.0) - ln_gamma(k as f64 + 1.0) - ln_gamma((n - k) as f64 + 1.0)
+}
+
+/// Cuantil `probabilidad` (entre 0 y 1, sin incluirlos) de la distribución normal
+/// estándar, con la aproximación de Acklam.
+fn cuantil_normal(probabilidad: f64) -> f64 {
+    if probabilidad < PROBABILIDAD_COLA {
+        let q = (-2.0 * probabilidad.ln()).sqrt();
+        evaluar_polinomio(&COEFICIENTES_C, q) / evaluar_polinomio(&COEFICIENTES_D, q)
+    } else if probabilidad <= 1.0 - PROBABILIDAD_COLA {
+        let q = probabilidad - 0.5;
+        let r = q * q;
+        q * evaluar_polinomio(&COEFICIENTES_A, r) / evaluar_polinomio(&COEFICIENTES_B, r)
+    } else {
+        -cuantil_normal(1.0 - probabilidad)
+    }
+}
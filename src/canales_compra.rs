@@ -0,0 +1,189 @@
+//! Este módulo contiene el desglose de las reseñas de cada juego según cómo obtuvo el
+//! juego su autor (columnas `steam_purchase` y `received_for_free`), y la detección de
+//! los juegos cuyas reseñas de copias gratuitas son más positivas que las de compras
+//! (en Steam o con una clave).
+
+// Imports de crates externas.
+use serde::Serialize;
+
+// Imports de funciones/estructuras propias.
+use crate::calificacion::{ConteoRecomendaciones, OpcionesCalificacion};
+
+/// Canal por el que el autor de una reseña obtuvo el juego.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanalCompra {
+    /// Comprado en Steam (`steam_purchase`).
+    Steam,
+    /// Activado con una clave comprada fuera de Steam.
+    Clave,
+    /// Recibido gratis (`received_for_free`).
+    Gratis,
+}
+
+/// Reseñas de un juego obtenidas por un canal.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ConteoCanal {
+    pub cantidad: u64,
+    pub recomendaciones: ConteoRecomendaciones,
+}
+
+/// Reseñas de un juego separadas por el canal por el que sus autores lo obtuvieron.
+///
+/// Las reseñas sin canal (ver `CanalCompra::desde_reseña`) no se cuentan en ninguno.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CanalesCompra {
+    pub steam: ConteoCanal,
+    pub clave: ConteoCanal,
+    pub gratis: ConteoCanal,
+}
+
+/// Diferencia entre las reseñas de copias gratuitas de un juego y las de copias compradas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SesgoGratis {
+    /// Porcentaje de positivas de las copias gratuitas menos el de las copias compradas.
+    pub diferencia_porcentual: f64,
+    /// Valor p de la prueba exacta de Fisher (unilateral) de que las copias gratuitas son
+    /// más positivas que las compradas.
+    pub valor_p: f64,
+    /// Valor p ajustado por la cantidad de juegos comparados (Benjamini-Hochberg).
+    pub valor_p_ajustado: f64,
+    /// Si las copias gratuitas son significativamente más positivas, con el nivel de
+    /// confianza de `OpcionesCalificacion`.
+    pub significativo: bool,
+}
+
+impl CanalCompra {
+    /// Canal de una reseña: las copias recibidas gratis se cuentan como tales aunque se
+    /// hayan activado en Steam; el resto, como compradas en Steam o activadas con una
+    /// clave según `steam_purchase`. Devuelve `None` si no se conoce el canal.
+    pub fn desde_reseña(
+        compra_en_steam: Option<bool>,
+        recibido_gratis: Option<bool>,
+    ) -> Option<Self> {
+        if recibido_gratis == Some(true) {
+            return Some(CanalCompra::Gratis);
+        }
+
+        compra_en_steam.map(|en_steam| {
+            if en_steam {
+                CanalCompra::Steam
+            } else {
+                CanalCompra::Clave
+            }
+        })
+    }
+}
+
+impl ConteoCanal {
+    /// Suma las reseñas de `otro` a las de `self`.
+    fn sumar(&mut self, otro: &ConteoCanal) {
+        self.cantidad += otro.cantidad;
+        self.recomendaciones.sumar(&otro.recomendaciones);
+    }
+}
+
+impl CanalesCompra {
+    /// Registra una reseña en el canal por el que se obtuvo el juego, si se conoce.
+    pub fn registrar(&mut self, canal: Option<CanalCompra>, recomendada: Option<bool>) {
+        let conteo = match canal {
+            Some(CanalCompra::Steam) => &mut self.steam,
+            Some(CanalCompra::Clave) => &mut self.clave,
+            Some(CanalCompra::Gratis) => &mut self.gratis,
+            None => return,
+        };
+
+        conteo.cantidad += 1;
+        conteo.recomendaciones.registrar(recomendada);
+    }
+
+    /// Fusiona las reseñas de `self` en `destino`.
+    pub fn merge_into(self, destino: &mut CanalesCompra) {
+        destino.steam.sumar(&self.steam);
+        destino.clave.sumar(&self.clave);
+        destino.gratis.sumar(&self.gratis);
+    }
+
+    /// Recomendaciones de las copias compradas, en Steam o con una clave.
+    pub fn compradas(&self) -> ConteoRecomendaciones {
+        let mut compradas = self.steam.recomendaciones;
+        compradas.sumar(&self.clave.recomendaciones);
+        compradas
+    }
+
+    /// Compara las reseñas de copias gratuitas con las de copias compradas (en Steam o con
+    /// una clave), con la prueba exacta de Fisher (unilateral).
+    ///
+    /// Devuelve la diferencia de porcentajes de positivas y el valor p, o `None` si alguno
+    /// de los dos grupos tiene menos de `minimo_reseñas` reseñas con recomendación.
+    fn comparar_gratis(&self, minimo_reseñas: u64) -> Option<(f64, f64)> {
+        let (gratis, compradas) = (&self.gratis.recomendaciones, &self.compradas());
+        if gratis.total() < minimo_reseñas || compradas.total() < minimo_reseñas {
+            return None;
+        }
+
+        let diferencia = gratis.porcentaje_positivo()? - compradas.porcentaje_positivo()?;
+        Some((diferencia, gratis.valor_p_mas_positivas(compradas)))
+    }
+}
+
+/// Compara las reseñas de copias gratuitas de cada juego con las de copias compradas.
+///
+/// Como se prueba un juego por vez, los valores p se ajustan por la cantidad de juegos
+/// comparados con el procedimiento de Benjamini-Hochberg, y un juego se marca con sesgo
+/// si su valor ajustado no supera `1 - opciones.confianza`: así, la proporción esperada
+/// de falsos sesgos entre los juegos marcados es, a lo sumo, ese valor. Los juegos con
+/// menos de `opciones.minimo_reseñas` reseñas con recomendación en alguno de los grupos
+/// no se comparan ni cuentan para el ajuste.
+///
+/// Devuelve los juegos comparados con su sesgo, en el mismo orden en el que se recibieron.
+pub fn evaluar_sesgos<'a, K>(
+    juegos: impl IntoIterator<Item = (K, &'a CanalesCompra)>,
+    opciones: &OpcionesCalificacion,
+) -> Vec<(K, SesgoGratis)> {
+    let comparados: Vec<_> = juegos
+        .into_iter()
+        .filter_map(|(juego, canales)| {
+            Some((juego, canales.comparar_gratis(opciones.minimo_reseñas)?))
+        })
+        .collect();
+    let valores_p: Vec<_> = comparados
+        .iter()
+        .map(|(_, (_, valor_p))| *valor_p)
+        .collect();
+    let ajustados = ajustar_benjamini_hochberg(&valores_p);
+
+    comparados
+        .into_iter()
+        .zip(ajustados)
+        .map(|((juego, (diferencia, valor_p)), valor_p_ajustado)| {
+            let sesgo = SesgoGratis {
+                diferencia_porcentual: diferencia,
+                valor_p,
+                valor_p_ajustado,
+                significativo: valor_p_ajustado <= 1.0 - opciones.confianza,
+            };
+            (juego, sesgo)
+        })
+        .collect()
+}
+
+/// Valores p ajustados con el procedimiento de Benjamini-Hochberg, en el mismo orden que
+/// `valores_p`: el ajustado del `i`-ésimo menor de los `m` valores es el mínimo de
+/// `p * m / j` entre él y los mayores (de rango `j`), acotado a 1.
+///
+/// Los valores p iguales reciben el mismo valor ajustado, por lo que el resultado no
+/// depende del orden en que se reciben.
+fn ajustar_benjamini_hochberg(valores_p: &[f64]) -> Vec<f64> {
+    let cantidad = valores_p.len() as f64;
+    let mut orden: Vec<_> = (0..valores_p.len()).collect();
+    orden.sort_by(|&a, &b| valores_p[a].total_cmp(&valores_p[b]));
+
+    let mut ajustados = vec![0.0; valores_p.len()];
+    let mut minimo = 1.0_f64;
+    for (rango, &indice) in orden.iter().enumerate().rev() {
+        minimo = minimo.min(valores_p[indice] * cantidad / (rango + 1) as f64);
+        ajustados[indice] = minimo;
+    }
+
+    ajustados
+}
@@ -0,0 +1,97 @@
+//! Este módulo contiene el índice de reseñas usado para descartar las reseñas
+//! repetidas (mismo `review_id`) entre archivos y chunks.
+
+// Imports de crates externas.
+use std::collections::HashMap;
+
+// Imports de funciones/estructuras propias.
+use crate::reviews_parseadas::MarcaTemporal;
+
+/// Posición de una reseña dentro de la entrada: (índice del archivo, línea en el archivo).
+pub type PosicionReseña = (usize, u64);
+
+/// Copia de una reseña que, por ahora, es la que se conserva.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CopiaGanadora {
+    actualizada: Option<MarcaTemporal>,
+    posicion: PosicionReseña,
+}
+
+impl CopiaGanadora {
+    /// Indica si `self` le gana a `otra`: gana la actualizada más recientemente y, ante
+    /// un empate, la que aparece primero en la entrada.
+    fn gana_a(&self, otra: &CopiaGanadora) -> bool {
+        self.actualizada > otra.actualizada
+            || (self.actualizada == otra.actualizada && self.posicion < otra.posicion)
+    }
+}
+
+/// Índice que guarda, por cada `review_id`, la copia de la reseña que se conserva.
+///
+/// Como el criterio no depende del orden en que se registran las copias, los índices
+/// parciales de cada chunk se pueden construir en paralelo y mergear en cualquier orden.
+#[derive(Debug, Default)]
+pub struct IndiceReseñas {
+    ganadoras: HashMap<u64, CopiaGanadora>,
+}
+
+impl IndiceReseñas {
+    /// Registra una copia de una reseña.
+    ///
+    /// # Argumentos
+    /// * `id` - `review_id` de la reseña.
+    /// * `actualizada` - `timestamp_updated` de la copia, si se conoce.
+    /// * `posicion` - Posición de la copia dentro de la entrada.
+    pub fn registrar(
+        &mut self,
+        id: u64,
+        actualizada: Option<MarcaTemporal>,
+        posicion: PosicionReseña,
+    ) {
+        self.registrar_copia(
+            id,
+            CopiaGanadora {
+                actualizada,
+                posicion,
+            },
+        );
+    }
+
+    /// Registra una copia, reemplazando a la actual si le gana.
+    fn registrar_copia(&mut self, id: u64, copia: CopiaGanadora) {
+        self.ganadoras
+            .entry(id)
+            .and_modify(|actual| {
+                if copia.gana_a(actual) {
+                    *actual = copia;
+                }
+            })
+            .or_insert(copia);
+    }
+
+    /// Fusiona el índice de `self` en `destino`.
+    pub fn merge_into(self, destino: &mut IndiceReseñas) {
+        for (id, copia) in self.ganadoras {
+            destino.registrar_copia(id, copia);
+        }
+    }
+
+    /// Combina dos índices en uno, fusionando el más chico en el más grande.
+    pub fn combinar(self, otro: IndiceReseñas) -> IndiceReseñas {
+        let (menor, mut mayor) = if self.ganadoras.len() < otro.ganadoras.len() {
+            (self, otro)
+        } else {
+            (otro, self)
+        };
+
+        menor.merge_into(&mut mayor);
+        mayor
+    }
+
+    /// Indica si la copia de la reseña en `posicion` es la que se conserva.
+    pub fn es_ganadora(&self, id: u64, posicion: PosicionReseña) -> bool {
+        self.ganadoras
+            .get(&id)
+            .is_none_or(|ganadora| ganadora.posicion == posicion)
+    }
+}
@@ -0,0 +1,251 @@
+//! Este módulo contiene la lógica de búsqueda de los archivos a procesar a partir
+//! de las rutas de entrada (archivos sueltos y directorios), y de su apertura
+//! (descomprimiéndolos si hace falta).
+
+// Imports de crates externas.
+use flate2::read::MultiGzDecoder;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::{File, read_dir};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+// Imports de funciones/estructuras propias.
+use crate::reporte_ingesta::ErrorIngesta;
+
+// Constantes.
+const EXTENSION_ARCHIVO_A_PROCESAR: &str = ".csv";
+const EXTENSION_GZIP: &str = ".csv.gz";
+const EXTENSION_ZSTD: &str = ".csv.zst";
+pub const RUTA_ENTRADA_ESTANDAR: &str = "-";
+
+// Mensajes.
+const ERROR_RUTA_INVALIDA: &str = "❌ La ruta de entrada no es un archivo ni un directorio válido:";
+const ERROR_PATRON_INVALIDO: &str = "❌ Patrón inválido";
+const ERROR_LEER_DIRECTORIO: &str = "⚠️ Error al leer el directorio";
+const ERROR_ENTRADA_DIRECTORIO: &str = "⚠️ Error al leer entrada del directorio:";
+
+/// Opciones de búsqueda de los archivos de entrada.
+#[derive(Debug, Clone, Default)]
+pub struct OpcionesEntrada {
+    /// Si es `true`, los directorios se recorren junto a todos sus subdirectorios.
+    pub recursivo: bool,
+    /// Patrones 'glob' de los archivos a incluir. Si está vacío, se incluyen todos.
+    pub incluir: Vec<String>,
+    /// Patrones 'glob' de los archivos a excluir.
+    pub excluir: Vec<String>,
+}
+
+/// Compresión de un archivo de entrada, deducida de su extensión.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compresion {
+    /// `.csv` sin comprimir.
+    Ninguna,
+    /// `.csv.gz`.
+    Gzip,
+    /// `.csv.zst`.
+    Zstd,
+}
+
+impl Compresion {
+    /// Deduce la compresión de un archivo a partir de su nombre.
+    ///
+    /// # Retorna
+    /// * `Some(Compresion)` si la extensión es una de las que el programa sabe procesar,
+    ///   o `None` si no lo es.
+    pub fn desde_ruta(ruta: &Path) -> Option<Self> {
+        let nombre = ruta.file_name()?.to_str()?;
+        if nombre.ends_with(EXTENSION_ARCHIVO_A_PROCESAR) {
+            Some(Compresion::Ninguna)
+        } else if nombre.ends_with(EXTENSION_GZIP) {
+            Some(Compresion::Gzip)
+        } else if nombre.ends_with(EXTENSION_ZSTD) {
+            Some(Compresion::Zstd)
+        } else {
+            None
+        }
+    }
+}
+
+/// Abre un archivo de entrada, descomprimiéndolo al vuelo según su extensión.
+///
+/// Los archivos cuya extensión no es conocida (por ejemplo, los indicados explícitamente
+/// con otro nombre) se leen como `.csv` sin comprimir. La ruta `RUTA_ENTRADA_ESTANDAR`
+/// lee el `.csv` (sin comprimir) de la entrada estándar.
+///
+/// # Argumentos
+/// * `ruta` - Ruta del archivo a abrir.
+///
+/// # Retorna
+/// * `Ok(Box<dyn Read + Send>)` con el contenido del `.csv` ya descomprimido, o el error
+///   al abrir el archivo.
+pub fn abrir_archivo(ruta: &Path) -> io::Result<Box<dyn Read + Send>> {
+    if ruta == Path::new(RUTA_ENTRADA_ESTANDAR) {
+        return Ok(Box::new(io::stdin()));
+    }
+
+    let archivo = File::open(ruta)?;
+    Ok(
+        match Compresion::desde_ruta(ruta).unwrap_or(Compresion::Ninguna) {
+            Compresion::Ninguna => Box::new(archivo),
+            Compresion::Gzip => Box::new(MultiGzDecoder::new(archivo)),
+            Compresion::Zstd => Box::new(zstd::Decoder::new(archivo)?),
+        },
+    )
+}
+
+/// Filtro de patrones 'glob' compilados a partir de las `OpcionesEntrada`.
+///
+/// Los patrones se comparan contra la ruta del archivo relativa al directorio de
+/// entrada en el que se encontró (por ejemplo, `2021/enero/reviews.csv`).
+struct FiltroArchivos {
+    incluir: Option<GlobSet>,
+    excluir: GlobSet,
+}
+
+impl FiltroArchivos {
+    /// Compila los patrones de inclusión y exclusión.
+    fn nuevo(opciones: &OpcionesEntrada) -> Result<Self, Box<dyn Error>> {
+        let incluir = if opciones.incluir.is_empty() {
+            None
+        } else {
+            Some(compilar_patrones(&opciones.incluir)?)
+        };
+
+        Ok(FiltroArchivos {
+            incluir,
+            excluir: compilar_patrones(&opciones.excluir)?,
+        })
+    }
+
+    /// Indica si un archivo, dado por su ruta relativa, pasa el filtro.
+    fn acepta(&self, relativa: &Path) -> bool {
+        self.incluir
+            .as_ref()
+            .is_none_or(|incluir| incluir.is_match(relativa))
+            && !self.excluir.is_match(relativa)
+    }
+}
+
+/// Compila una lista de patrones 'glob' en un único `GlobSet`.
+fn compilar_patrones(patrones: &[String]) -> Result<GlobSet, Box<dyn Error>> {
+    let mut builder = GlobSetBuilder::new();
+    for patron in patrones {
+        let glob = Glob::new(patron)
+            .map_err(|e| format!("{} '{}': {}", ERROR_PATRON_INVALIDO, patron, e))?;
+
+        builder.add(glob);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Recorre un directorio agregando a `encontrados` los archivos que pasan el filtro.
+///
+/// # Argumentos
+/// * `raiz` - Directorio de entrada, contra el que se calculan las rutas relativas.
+/// * `directorio` - Directorio a recorrer (la raíz o alguno de sus subdirectorios).
+/// * `opciones` - Opciones de búsqueda.
+/// * `filtro` - Filtro de patrones compilado.
+/// * `estricto` - Si es `true`, una entrada o subdirectorio ilegible es un error.
+/// * `encontrados` - Lista a la que se agregan los archivos encontrados.
+fn recorrer_directorio(
+    raiz: &Path,
+    directorio: &Path,
+    opciones: &OpcionesEntrada,
+    filtro: &FiltroArchivos,
+    estricto: bool,
+    encontrados: &mut Vec<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let entradas = match read_dir(directorio) {
+        Ok(entradas) => entradas,
+        Err(e) if directorio == raiz => return Err(e.into()),
+        Err(e) if estricto => {
+            return Err(Box::new(ErrorIngesta::EntradaIlegible {
+                directorio: directorio.display().to_string(),
+                detalle: e.to_string(),
+            }));
+        }
+        Err(e) => {
+            eprintln!("{} {}: {}", ERROR_LEER_DIRECTORIO, directorio.display(), e);
+            return Ok(());
+        }
+    };
+
+    for entry in entradas {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) if estricto => {
+                return Err(Box::new(ErrorIngesta::EntradaIlegible {
+                    directorio: directorio.display().to_string(),
+                    detalle: e.to_string(),
+                }));
+            }
+            Err(e) => {
+                eprintln!("{} {}", ERROR_ENTRADA_DIRECTORIO, e);
+                continue;
+            }
+        };
+
+        // No se siguen los enlaces simbólicos a directorios, para evitar ciclos.
+        let ruta = entry.path();
+        if entry.file_type().is_ok_and(|tipo| tipo.is_dir()) {
+            if opciones.recursivo {
+                recorrer_directorio(raiz, &ruta, opciones, filtro, estricto, encontrados)?;
+            }
+
+            continue;
+        }
+
+        let relativa = ruta.strip_prefix(raiz).unwrap_or(&ruta);
+        if ruta.is_file() && Compresion::desde_ruta(&ruta).is_some() && filtro.acepta(relativa) {
+            encontrados.push(ruta);
+        }
+    }
+
+    Ok(())
+}
+
+/// Busca los archivos a procesar a partir de las rutas de entrada.
+///
+/// Los archivos indicados explícitamente (y la entrada estándar, indicada con
+/// `RUTA_ENTRADA_ESTANDAR`) se procesan siempre; los de los directorios se filtran por
+/// extensión (`.csv`, `.csv.gz` o `.csv.zst`) y por los patrones de `opciones`. El orden
+/// resultante es el de las rutas de entrada y, dentro de cada directorio, el orden
+/// alfabético de las rutas, por lo que no depende del orden en que el sistema de
+/// archivos lista los directorios.
+///
+/// # Argumentos
+/// * `rutas` - Rutas de entrada (archivos o directorios).
+/// * `opciones` - Opciones de búsqueda.
+/// * `estricto` - Si es `true`, una entrada de directorio ilegible es un error.
+///
+/// # Retorna
+/// * `Ok(Vec<PathBuf>)` con los archivos a procesar, sin repetidos, o un error si alguna
+///   ruta no es válida.
+pub fn buscar_archivos(
+    rutas: &[String],
+    opciones: &OpcionesEntrada,
+    estricto: bool,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let filtro = FiltroArchivos::nuevo(opciones)?;
+    let mut archivos = Vec::new();
+    for ruta in rutas {
+        let path = Path::new(ruta);
+        if ruta == RUTA_ENTRADA_ESTANDAR || path.is_file() {
+            archivos.push(path.to_path_buf());
+        } else if path.is_dir() {
+            let mut encontrados = Vec::new();
+            recorrer_directorio(path, path, opciones, &filtro, estricto, &mut encontrados)?;
+            encontrados.sort();
+            archivos.extend(encontrados);
+        } else {
+            return Err(format!("{} '{}'", ERROR_RUTA_INVALIDA, ruta).into());
+        }
+    }
+
+    let mut vistos = HashSet::new();
+    archivos.retain(|archivo| vistos.insert(archivo.clone()));
+    Ok(archivos)
+}
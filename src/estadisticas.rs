@@ -1,78 +1,438 @@
 //! Este módulo contiene la lógica de las estadísticas internas del programa.
 
 // Imports de crates externas.
+use serde::Serialize;
+use std::cmp::{Ordering, Reverse};
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::sync::Arc;
 
-// Constantes.
-const MAXIMA_CANTIDAD_REVIEWS: usize = 10;
+// Imports de funciones/estructuras propias.
+use crate::acceso_anticipado::FasesLanzamiento;
+use crate::calificacion::ConteoRecomendaciones;
+use crate::canales_compra::CanalesCompra;
+use crate::hyperloglog::HyperLogLog;
+use crate::linea_de_tiempo::LineaDeTiempo;
+use crate::metricas::{RankingReseñas, ValorMetrica};
+use crate::reporte_ingesta::ReporteIngesta;
+use crate::reviews_parseadas::MarcaTemporal;
+use crate::tiempo_de_juego::TiempoDeJuegoPorRecomendacion;
+use crate::top_k::TopK;
+
+/// Cantidad de votos útiles de una reseña.
+///
+/// Se usa `u64` para poder representar los votos que no entran en un `u32` cuando la
+/// política de votos desbordados es `PoliticaVotosDesbordados::Ampliar`.
+pub type Votos = u64;
+
+/// Clave con la que se agrupan las reseñas de un mismo juego.
+///
+/// Se agrupa por `app_id`, ya que un juego puede cambiar de nombre con el tiempo y dos
+/// juegos distintos pueden compartir nombre. Las reseñas sin `app_id` se agrupan por nombre.
+///
+/// Los nombres de los juegos y los idiomas se guardan internados (ver `Internador`), y
+/// recién se convierten a `String` al serializar las estadísticas.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ClaveJuego {
+    Id(u64),
+    SinId(Arc<str>),
+}
+
+/// Reseña candidata a figurar entre las mejores de un juego o idioma.
+///
+/// Su orden (`Ord`) es el del ranking, de mejor a peor: primero la de mayor valor de la
+/// métrica del ranking (ver `MetricaReseñas::valor`), luego
+/// la de menor `review_id` (las que no lo tienen, al final) y, por último, por texto.
+/// Al ser un orden total, el ranking no depende del orden en que se procesan las reseñas.
+///
+/// Las estadísticas guardan el texto en un `String`, pero una candidata puede tener su
+/// texto prestado (`&str`) y compararse igual contra las guardadas, para copiarlo sólo si
+/// entra al ranking.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReseñaDestacada<T = String> {
+    pub texto: T,
+    /// Valor de la reseña según la métrica del ranking.
+    pub valor: ValorMetrica,
+    pub id: Option<u64>,
+}
+
+impl ClaveJuego {
+    /// `app_id` del juego, si sus reseñas lo tienen.
+    pub fn id(&self) -> Option<u64> {
+        match self {
+            ClaveJuego::Id(id) => Some(*id),
+            ClaveJuego::SinId(_) => None,
+        }
+    }
+}
+
+impl<T: AsRef<str>> ReseñaDestacada<T> {
+    /// Clave con la que se ordenan las reseñas.
+    fn clave(&self) -> (Reverse<ValorMetrica>, bool, Option<u64>, &str) {
+        (
+            Reverse(self.valor),
+            self.id.is_none(),
+            self.id,
+            self.texto.as_ref(),
+        )
+    }
+
+    /// Copia el texto de la reseña, si era prestado.
+    pub fn con_texto_propio(self) -> ReseñaDestacada
+    where
+        T: Into<String>,
+    {
+        ReseñaDestacada {
+            texto: self.texto.into(),
+            valor: self.valor,
+            id: self.id,
+        }
+    }
+}
+
+impl<T: AsRef<str>, U: AsRef<str>> PartialEq<ReseñaDestacada<U>> for ReseñaDestacada<T> {
+    fn eq(&self, otra: &ReseñaDestacada<U>) -> bool {
+        self.clave() == otra.clave()
+    }
+}
+
+impl<T: AsRef<str>> Eq for ReseñaDestacada<T> {}
+
+impl<T: AsRef<str>, U: AsRef<str>> PartialOrd<ReseñaDestacada<U>> for ReseñaDestacada<T> {
+    fn partial_cmp(&self, otra: &ReseñaDestacada<U>) -> Option<Ordering> {
+        Some(self.clave().cmp(&otra.clave()))
+    }
+}
+
+impl<T: AsRef<str>> Ord for ReseñaDestacada<T> {
+    fn cmp(&self, otra: &Self) -> Ordering {
+        self.clave().cmp(&otra.clave())
+    }
+}
 
 /// Estructura que guarda la información global de:
 /// - Juegos.
 /// - Idiomas.
-#[derive(Debug, Default)]
+/// - Ingesta de los archivos (filas aceptadas y rechazadas).
+/// - Autores distintos de todas las reseñas (`author.steamid`).
+#[derive(Debug, Default, PartialEq)]
 pub struct EstadisticasGlobales {
-    pub juegos: HashMap<String, InfoJuego>,
-    pub por_idioma: HashMap<String, InfoIdioma>,
+    pub juegos: HashMap<ClaveJuego, InfoJuego>,
+    pub por_idioma: HashMap<Arc<str>, InfoIdioma>,
+    pub ingesta: ReporteIngesta,
+    pub autores: HyperLogLog,
 }
 
 /// Estructura que guarda la información de un juego procesado.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub struct InfoJuego {
     pub cantidad_total: usize,
-    pub por_idioma: HashMap<String, usize>,
-    pub mejores_reviews: HashMap<String, (String, u32)>,
+    pub nombres: HashMap<Arc<str>, AparicionesNombre>,
+    pub por_idioma: HashMap<Arc<str>, usize>,
+    pub mejores_reviews: HashMap<Arc<str>, ReseñaDestacada>,
+    pub mas_graciosas: HashMap<Arc<str>, ReseñaDestacada>,
+    pub mas_comentadas: HashMap<Arc<str>, ReseñaDestacada>,
+    pub recomendaciones: ConteoRecomendaciones,
+    pub recomendaciones_por_idioma: HashMap<Arc<str>, ConteoRecomendaciones>,
+    pub linea_de_tiempo: LineaDeTiempo,
+    pub tiempo_de_juego: TiempoDeJuegoPorRecomendacion,
+    pub autores: HyperLogLog,
+    pub fases_lanzamiento: FasesLanzamiento,
+    pub canales_compra: CanalesCompra,
+}
+
+/// Estructura que guarda cuántas reseñas de un juego usaron un nombre, y cuándo fue la
+/// más reciente.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AparicionesNombre {
+    pub cantidad: usize,
+    pub ultima_vez: Option<MarcaTemporal>,
 }
 
 /// Estructura que guarda la información de un idioma procesado.
-#[derive(Debug, Default)]
+///
+/// Sólo se guardan las mejores reseñas del idioma que se van a mostrar (ver
+/// `LimitesRanking::reviews_por_idioma`) y la más graciosa y la más comentada, por lo que
+/// su tamaño no depende de la cantidad de reseñas procesadas.
+#[derive(Debug)]
 pub struct InfoIdioma {
     pub cantidad_total: usize,
-    pub top_reviews: Vec<(String, u32)>,
+    pub top_reviews: TopK<ReseñaDestacada>,
+    pub mas_graciosa: TopK<ReseñaDestacada>,
+    pub mas_comentada: TopK<ReseñaDestacada>,
+    pub linea_de_tiempo: LineaDeTiempo,
+    pub autores: HyperLogLog,
+}
+
+impl InfoIdioma {
+    /// Crea la información de un idioma que guarda, como máximo, `capacidad_top` reseñas.
+    pub fn nuevo(capacidad_top: usize) -> Self {
+        InfoIdioma {
+            cantidad_total: 0,
+            top_reviews: TopK::nuevo(capacidad_top),
+            mas_graciosa: TopK::nuevo(1),
+            mas_comentada: TopK::nuevo(1),
+            linea_de_tiempo: LineaDeTiempo::default(),
+            autores: HyperLogLog::default(),
+        }
+    }
+
+    /// Reseñas destacadas del idioma en un ranking.
+    pub fn destacadas(&self, ranking: RankingReseñas) -> &TopK<ReseñaDestacada> {
+        match ranking {
+            RankingReseñas::Mejores => &self.top_reviews,
+            RankingReseñas::MasGraciosas => &self.mas_graciosa,
+            RankingReseñas::MasComentadas => &self.mas_comentada,
+        }
+    }
+
+    /// Reseñas destacadas del idioma en un ranking, para registrar nuevas.
+    pub fn destacadas_mut(&mut self, ranking: RankingReseñas) -> &mut TopK<ReseñaDestacada> {
+        match ranking {
+            RankingReseñas::Mejores => &mut self.top_reviews,
+            RankingReseñas::MasGraciosas => &mut self.mas_graciosa,
+            RankingReseñas::MasComentadas => &mut self.mas_comentada,
+        }
+    }
+}
+
+impl PartialEq for InfoIdioma {
+    fn eq(&self, otra: &Self) -> bool {
+        self.cantidad_total == otra.cantidad_total
+            && RankingReseñas::TODOS.iter().all(|ranking| {
+                self.destacadas(*ranking).ordenados() == otra.destacadas(*ranking).ordenados()
+            })
+            && self.linea_de_tiempo == otra.linea_de_tiempo
+            && self.autores == otra.autores
+    }
+}
+
+impl AparicionesNombre {
+    /// Suma las apariciones de `otras` a las de `self`.
+    fn sumar(&mut self, otras: &AparicionesNombre) {
+        self.cantidad += otras.cantidad;
+        self.ultima_vez = self.ultima_vez.max(otras.ultima_vez);
+    }
+}
+
+impl InfoJuego {
+    /// Registra el nombre con el que aparece el juego en una reseña.
+    ///
+    /// # Argumentos
+    /// * `nombre` - Nombre (internado) del juego en la reseña.
+    /// * `marca` - Fecha de la reseña, si se conoce.
+    pub fn registrar_nombre(&mut self, nombre: Arc<str>, marca: Option<MarcaTemporal>) {
+        let apariciones = AparicionesNombre {
+            cantidad: 1,
+            ultima_vez: marca,
+        };
+
+        self.nombres.entry(nombre).or_default().sumar(&apariciones);
+    }
+
+    /// Registra si una reseña del juego en `idioma` lo recomienda (`recommended`).
+    pub fn registrar_recomendacion(&mut self, idioma: &Arc<str>, recomendada: Option<bool>) {
+        if recomendada.is_none() {
+            return;
+        }
+
+        self.recomendaciones.registrar(recomendada);
+        match self.recomendaciones_por_idioma.get_mut(idioma) {
+            Some(conteo) => conteo.registrar(recomendada),
+            None => {
+                let mut conteo = ConteoRecomendaciones::default();
+                conteo.registrar(recomendada);
+                self.recomendaciones_por_idioma
+                    .insert(Arc::clone(idioma), conteo);
+            }
+        }
+    }
+
+    /// Reseña destacada del juego en cada idioma, en un ranking.
+    pub fn destacadas(&self, ranking: RankingReseñas) -> &HashMap<Arc<str>, ReseñaDestacada> {
+        match ranking {
+            RankingReseñas::Mejores => &self.mejores_reviews,
+            RankingReseñas::MasGraciosas => &self.mas_graciosas,
+            RankingReseñas::MasComentadas => &self.mas_comentadas,
+        }
+    }
+
+    /// Reseña destacada del juego en cada idioma, en un ranking, para registrar nuevas.
+    fn destacadas_mut(
+        &mut self,
+        ranking: RankingReseñas,
+    ) -> &mut HashMap<Arc<str>, ReseñaDestacada> {
+        match ranking {
+            RankingReseñas::Mejores => &mut self.mejores_reviews,
+            RankingReseñas::MasGraciosas => &mut self.mas_graciosas,
+            RankingReseñas::MasComentadas => &mut self.mas_comentadas,
+        }
+    }
+
+    /// Indica si `reseña` sería la destacada del juego en `idioma` en un ranking, sin
+    /// registrarla.
+    pub fn admite_destacada<T: AsRef<str>>(
+        &self,
+        ranking: RankingReseñas,
+        idioma: &str,
+        reseña: &ReseñaDestacada<T>,
+    ) -> bool {
+        self.destacadas(ranking)
+            .get(idioma)
+            .is_none_or(|actual| reseña < actual)
+    }
+
+    /// Registra una reseña del juego en un idioma en un ranking, quedándose con la mejor
+    /// según el orden de `ReseñaDestacada`.
+    pub fn registrar_destacada(
+        &mut self,
+        ranking: RankingReseñas,
+        idioma: &Arc<str>,
+        reseña: ReseñaDestacada,
+    ) {
+        let destacadas = self.destacadas_mut(ranking);
+        match destacadas.get_mut(idioma) {
+            Some(actual) => {
+                if reseña < *actual {
+                    *actual = reseña;
+                }
+            }
+
+            None => {
+                destacadas.insert(Arc::clone(idioma), reseña);
+            }
+        }
+    }
+
+    /// Nombres con los que aparece el juego, del más usado al menos usado.
+    ///
+    /// Los empates se resuelven por el nombre usado más recientemente y, luego, por orden
+    /// alfabético, por lo que el orden no depende del orden de procesamiento.
+    pub fn alias(&self) -> Vec<&str> {
+        let mut nombres: Vec<_> = self.nombres.iter().collect();
+        nombres.sort_by(|(nombre_a, a), (nombre_b, b)| {
+            b.cantidad
+                .cmp(&a.cantidad)
+                .then(b.ultima_vez.cmp(&a.ultima_vez))
+                .then(nombre_a.cmp(nombre_b))
+        });
+
+        nombres.into_iter().map(|(nombre, _)| &**nombre).collect()
+    }
+
+    /// Nombre con el que se muestra el juego: el más usado en sus reseñas.
+    pub fn nombre_principal(&self) -> &str {
+        self.alias().first().copied().unwrap_or_default()
+    }
 }
 
 /// Métodos de mergeo de las estadísticas obtenidas.
+///
+/// Las estadísticas se consumen al fusionarlas, por lo que las claves y los textos de
+/// las reseñas se mueven al destino en lugar de clonarse. Además, el resultado no
+/// depende del orden en que se fusionan, lo que permite reducirlas en paralelo.
 impl EstadisticasGlobales {
     /// Método para fusionar estadísticas parciales en una global.
-    pub fn merge_into(&self, destino: &mut EstadisticasGlobales) {
-        self.merge_juegos(destino);
-        self.merge_idiomas(destino);
-    }
-
-    /// Fusiona la información de juegos de `self` en `destino`.
-    fn merge_juegos(&self, destino: &mut EstadisticasGlobales) {
-        for (juego, info) in &self.juegos {
-            let entry = destino.juegos.entry(juego.clone()).or_default();
-            entry.cantidad_total += info.cantidad_total;
-            for (idioma, count) in &info.por_idioma {
-                *entry.por_idioma.entry(idioma.clone()).or_insert(0) += *count;
-            }
+    pub fn merge_into(self, destino: &mut EstadisticasGlobales) {
+        merge_mapas(self.juegos, &mut destino.juegos, InfoJuego::merge_into);
+        merge_mapas(
+            self.por_idioma,
+            &mut destino.por_idioma,
+            InfoIdioma::merge_into,
+        );
+        self.ingesta.merge_into(&mut destino.ingesta);
+        self.autores.merge_into(&mut destino.autores);
+    }
+
+    /// Combina dos estadísticas parciales en una, fusionando la que tiene menos juegos
+    /// en la otra para mover la menor cantidad posible de entradas.
+    pub fn combinar(self, otra: EstadisticasGlobales) -> EstadisticasGlobales {
+        let (menor, mut mayor) = if self.juegos.len() < otra.juegos.len() {
+            (self, otra)
+        } else {
+            (otra, self)
+        };
+
+        menor.merge_into(&mut mayor);
+        mayor
+    }
+}
+
+impl InfoJuego {
+    /// Fusiona la información del juego de `self` en `destino`.
+    fn merge_into(self, destino: &mut InfoJuego) {
+        destino.cantidad_total += self.cantidad_total;
+        for (nombre, apariciones) in self.nombres {
+            destino
+                .nombres
+                .entry(nombre)
+                .or_default()
+                .sumar(&apariciones);
+        }
+
+        for (idioma, count) in self.por_idioma {
+            *destino.por_idioma.entry(idioma).or_insert(0) += count;
+        }
 
-            for (idioma, (texto, votos)) in &info.mejores_reviews {
-                match entry.mejores_reviews.entry(idioma.clone()) {
-                    std::collections::hash_map::Entry::Occupied(mut e) => {
-                        if *votos > e.get().1 {
-                            e.insert((texto.clone(), *votos));
-                        }
-                    }
-
-                    std::collections::hash_map::Entry::Vacant(e) => {
-                        e.insert((texto.clone(), *votos));
-                    }
+        for (ranking, destacadas) in [
+            (RankingReseñas::Mejores, self.mejores_reviews),
+            (RankingReseñas::MasGraciosas, self.mas_graciosas),
+            (RankingReseñas::MasComentadas, self.mas_comentadas),
+        ] {
+            for (idioma, reseña) in destacadas {
+                if destino.admite_destacada(ranking, &idioma, &reseña) {
+                    destino.destacadas_mut(ranking).insert(idioma, reseña);
                 }
             }
         }
+
+        destino.recomendaciones.sumar(&self.recomendaciones);
+        for (idioma, conteo) in self.recomendaciones_por_idioma {
+            destino
+                .recomendaciones_por_idioma
+                .entry(idioma)
+                .or_default()
+                .sumar(&conteo);
+        }
+
+        self.linea_de_tiempo
+            .merge_into(&mut destino.linea_de_tiempo);
+        self.tiempo_de_juego
+            .merge_into(&mut destino.tiempo_de_juego);
+        self.autores.merge_into(&mut destino.autores);
+        self.fases_lanzamiento
+            .merge_into(&mut destino.fases_lanzamiento);
+        self.canales_compra.merge_into(&mut destino.canales_compra);
     }
+}
 
-    /// Fusiona la información de idiomas de `self` en `destino`.
-    fn merge_idiomas(&self, destino: &mut EstadisticasGlobales) {
-        for (idioma, info) in &self.por_idioma {
-            let entry = destino.por_idioma.entry(idioma.clone()).or_default();
-            entry.cantidad_total += info.cantidad_total;
-            entry.top_reviews.extend(info.top_reviews.clone());
-            entry
-                .top_reviews
-                .sort_by_key(|(_, votos)| std::cmp::Reverse(*votos));
-            entry.top_reviews.truncate(MAXIMA_CANTIDAD_REVIEWS);
+impl InfoIdioma {
+    /// Fusiona la información del idioma de `self` en `destino`.
+    fn merge_into(self, destino: &mut InfoIdioma) {
+        destino.cantidad_total += self.cantidad_total;
+        self.top_reviews.merge_into(&mut destino.top_reviews);
+        self.mas_graciosa.merge_into(&mut destino.mas_graciosa);
+        self.mas_comentada.merge_into(&mut destino.mas_comentada);
+        self.linea_de_tiempo
+            .merge_into(&mut destino.linea_de_tiempo);
+        self.autores.merge_into(&mut destino.autores);
+    }
+}
+
+/// Fusiona las entradas de `origen` en `destino`: las claves que no están en `destino`
+/// se mueven tal cual, y las que sí están se fusionan con `merge`.
+fn merge_mapas<K: Eq + Hash, V>(
+    origen: HashMap<K, V>,
+    destino: &mut HashMap<K, V>,
+    merge: fn(V, &mut V),
+) {
+    for (clave, valor) in origen {
+        match destino.entry(clave) {
+            Entry::Occupied(entry) => merge(valor, entry.into_mut()),
+            Entry::Vacant(entry) => {
+                entry.insert(valor);
+            }
         }
     }
 }
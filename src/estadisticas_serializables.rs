@@ -1,46 +1,104 @@
 //! Este módulo contiene la lógica de las estadísticas finales y serializadas del programa.
+//!
+//! Es el único lugar donde los nombres internados de juegos e idiomas se vuelven a
+//! convertir en `String`.
 
 // Imports de crates externas.
 use serde::Serialize;
+use std::cmp::Reverse;
 use std::collections::HashMap;
 
 // Imports de funciones/estructuras propias.
-use crate::estadisticas::EstadisticasGlobales;
+use crate::acceso_anticipado::{FasesLanzamiento, ReseñasFase};
+use crate::boceto_cuantiles::BocetoCuantiles;
+use crate::calificacion::{ConteoRecomendaciones, OpcionesCalificacion};
+use crate::canales_compra::{CanalesCompra, ConteoCanal, SesgoGratis, evaluar_sesgos};
+use crate::estadisticas::{ClaveJuego, EstadisticasGlobales, InfoJuego};
+use crate::limites::LimitesRanking;
+use crate::linea_de_tiempo::{LineaDeTiempo, Periodo};
+use crate::metricas::{MetricaReseñas, RankingReseñas, ValorMetrica};
+use crate::procesadores::OpcionesProcesamiento;
+use crate::reporte_ingesta::ReporteIngesta;
+use crate::tiempo_de_juego::{TiempoDeJuego, TiempoDeJuegoPorRecomendacion};
 
 // Constantes.
-const MAX_TOP_JUEGOS: usize = 3;
-const MAX_TOP_REVIEWS_JUEGOS: usize = 3;
-const MAX_TOP_IDIOMAS: usize = 3;
-const MAX_TOP_REVIEWS_IDIOMAS: usize = 10;
+const CUANTIL_P10: f64 = 0.1;
+const CUANTIL_MEDIANA: f64 = 0.5;
+const CUANTIL_P90: f64 = 0.9;
 
 // Mensajes.
 const ERROR_INFORMACION_IDIOMAS: &str = "La información de idiomas debería estar presente";
 
 // Estructura usada para serializar la información de los juegos.
 /// Estructura que representa la información serializable de un juego, incluyendo
-/// el número total de reviews, las reviews por idioma y las mejores reviews.
+/// su nombre y alias, el número total de reviews, las reviews por idioma y las mejores,
+/// más graciosas y más comentadas reviews.
 #[derive(Debug, Serialize)]
 pub struct InfoJuegoSerializable {
+    pub nombre: String,
+    pub id_juego: Option<u64>,
+    pub alias: Vec<String>,
     pub cantidad_total: usize,
     pub por_idioma: HashMap<String, usize>,
     pub mejores_reviews: HashMap<String, MejorReview>,
+    pub mas_graciosas: HashMap<String, MejorReview>,
+    pub mas_comentadas: HashMap<String, MejorReview>,
+    pub recomendaciones: ConteoRecomendaciones,
+    pub recomendaciones_por_idioma: HashMap<String, ConteoRecomendaciones>,
+    pub linea_de_tiempo: LineaDeTiempo,
+    pub tiempo_de_juego: PlaytimeJuego,
+    pub autores_distintos: u64,
+    pub fases_lanzamiento: FasesLanzamiento,
+    pub canales_compra: CanalesCompra,
+    pub sesgo_gratis: Option<SesgoGratis>,
+}
+
+// Estructura usada para serializar la información de los juegos mejor calificados.
+/// Estructura que representa la información serializable de un juego del ranking por
+/// calificación, con su nombre, el número total de reviews, sus recomendaciones y el
+/// límite inferior de Wilson con el que se ordena.
+#[derive(Debug, Serialize)]
+pub struct JuegoCalificadoSerializable {
+    pub nombre: String,
+    pub id_juego: Option<u64>,
+    pub cantidad_total: usize,
+    pub recomendaciones: ConteoRecomendaciones,
+    pub limite_inferior_wilson: f64,
+}
+
+// Estructura usada para serializar la información de los juegos con sesgo de copias gratuitas.
+/// Estructura que representa la información serializable de un juego cuyas reviews de
+/// copias gratuitas son significativamente más positivas que las de copias compradas, con
+/// su nombre, sus reviews por canal de compra y la comparación entre ambos grupos.
+#[derive(Debug, Serialize)]
+pub struct JuegoSesgadoSerializable {
+    pub nombre: String,
+    pub id_juego: Option<u64>,
+    pub canales_compra: CanalesCompra,
+    pub sesgo_gratis: SesgoGratis,
 }
 
 // Estructura usada para serializar la información de los idiomas.
 /// Estructura que representa la información serializable de un idioma, incluyendo
-/// el número total de reviews y el top de mejores reviews para ese idioma.
+/// el número total de reviews, el top de mejores reviews para ese idioma y su review más
+/// graciosa y más comentada.
 #[derive(Debug, Serialize)]
 pub struct InfoIdiomaSerializable {
     pub cantidad_total: usize,
     pub top_reviews: Vec<MejorReview>,
+    pub mas_graciosa: Option<MejorReview>,
+    pub mas_comentada: Option<MejorReview>,
+    pub linea_de_tiempo: LineaDeTiempo,
+    pub autores_distintos: u64,
 }
 
 // Estructura usada para serializar las reviews.
-/// Estructura que representa una review, con su texto y el número de votos que ha recibido.
+/// Estructura que representa una review, con su texto y su valor en la métrica del
+/// ranking.
 #[derive(Debug, Serialize, Clone)]
 pub struct MejorReview {
     pub texto: String,
-    pub votos: u32,
+    pub valor: ValorMetrica,
 }
 
 // Estructura usada para formatear todo el resultado obtenido al ".json" final.
@@ -48,129 +106,599 @@ pub struct MejorReview {
 /// los juegos, idiomas y el top de reviews por idioma.
 #[derive(Debug, Serialize)]
 pub struct EstadisticasGlobalesSerializable {
-    pub juegos: HashMap<String, InfoJuegoSerializable>,
+    pub juegos: Vec<InfoJuegoSerializable>,
+    pub juegos_calificados: Vec<JuegoCalificadoSerializable>,
+    pub juegos_sesgados: Vec<JuegoSesgadoSerializable>,
+    pub juegos_comparados_por_canal: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub idiomas: Option<HashMap<String, InfoIdiomaSerializable>>,
     pub top_idiomas: HashMap<String, Vec<MejorReview>>,
+    pub autores_distintos: u64,
+    pub error_relativo_autores: Option<f64>,
+}
+
+// Estructura con las opciones que controlan qué secciones se escriben en el ".json".
+/// Estructura que representa las opciones de la salida final.
+#[derive(Debug, Default, Clone)]
+pub struct OpcionesSalida {
+    pub incluir_reporte_ingesta: bool,
+    pub calificacion: OpcionesCalificacion,
 }
 
 // Estructura para formatear el esquema general del ".json" con padrón incluido.
-/// Estructura que representa la salida final del programa, con el padrón y los
-/// juegos y idiomas más relevantes.
+/// Estructura que representa la salida final del programa, con el padrón, los
+/// juegos y idiomas más relevantes y, opcionalmente, el reporte de ingesta.
 #[derive(Serialize)]
 pub struct SalidaFinal {
     pub padron: u32,
+    pub vote_overflow_policy: &'static str,
+    pub review_metric: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicates_removed: Option<u64>,
+    pub unique_reviewers: AutoresDistintos,
     pub top_games: Vec<TopGame>,
+    pub top_rated_games: Vec<TopRatedGame>,
     pub top_languages: Vec<TopLanguage>,
+    pub purchase_channels: PurchaseChannels,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeline: Option<Timeline>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingestion_report: Option<Vec<IngestaArchivo>>,
+}
+
+// Estructura usada para mostrar en el ".json" los autores distintos de las reviews.
+/// Estructura que representa la cantidad estimada de autores distintos de todas las
+/// reviews, con el error estándar relativo de las estimaciones de autores distintos.
+#[derive(Serialize)]
+pub struct AutoresDistintos {
+    pub estimate: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relative_standard_error: Option<f64>,
 }
 
 // Estructura usada para mostrar en el ".json" los juegos con más reviews.
-/// Estructura que representa un juego en el top, con el nombre del juego, la cantidad
-/// de reviews y los idiomas con las mejores reviews.
+/// Estructura que representa un juego en el top, con el nombre del juego, su `app_id`,
+/// todos los nombres con los que aparece, la cantidad de reviews y los idiomas con las
+/// mejores reviews.
 #[derive(Serialize, PartialEq)]
 pub struct TopGame {
     pub game: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<u64>,
+    pub aliases: Vec<String>,
     pub review_count: u32,
+    pub unique_reviewers: u64,
+    pub recommendations: ResumenRecomendaciones,
+    pub playtime: PlaytimeJuego,
+    pub release_phases: FasesJuego,
     pub languages: Vec<IdiomaPorJuego>,
 }
 
 // Estructura auxiliar de 'TopGame'.
 /// Estructura que representa un idioma de un juego en el top, con el idioma, la cantidad
-/// de reviews y la mejor review del idioma.
+/// de reviews, sus recomendaciones y la mejor, la más graciosa y la más comentada review
+/// del idioma.
 #[derive(Serialize, PartialEq)]
 pub struct IdiomaPorJuego {
     pub language: String,
     pub review_count: u32,
+    pub recommendations: ResumenRecomendaciones,
     pub top_review: String,
-    pub top_review_votes: u32,
+    pub top_review_votes: ValorMetrica,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funniest_review: Option<ReviewGraciosa>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub most_discussed_review: Option<ReviewComentada>,
+}
+
+// Estructura auxiliar de 'IdiomaPorJuego' y 'TopLanguage'.
+/// Estructura que representa la review con más votos graciosos, con su texto y sus votos
+/// graciosos.
+#[derive(Serialize, PartialEq)]
+pub struct ReviewGraciosa {
+    pub review: String,
+    pub funny_votes: ValorMetrica,
+}
+
+// Estructura auxiliar de 'IdiomaPorJuego' y 'TopLanguage'.
+/// Estructura que representa la review con más comentarios, con su texto y su cantidad de
+/// comentarios.
+#[derive(Serialize, PartialEq)]
+pub struct ReviewComentada {
+    pub review: String,
+    pub comments: ValorMetrica,
+}
+
+// Estructura auxiliar de 'TopGame' e 'IdiomaPorJuego'.
+/// Estructura que representa las recomendaciones de un juego (o de un juego en un
+/// idioma), con las reviews positivas y negativas, el porcentaje de positivas y la
+/// calificación de Steam, si hay suficientes reviews para calcularla.
+#[derive(Serialize, PartialEq)]
+pub struct ResumenRecomendaciones {
+    pub positive: u64,
+    pub negative: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub positive_percentage: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<&'static str>,
+}
+
+// Estructura auxiliar de 'TopGame'.
+/// Estructura que representa el tiempo de juego de los autores de las reviews de un
+/// juego: de todas las reviews y de las que recomiendan y no recomiendan el juego.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PlaytimeJuego {
+    pub all: PlaytimeGrupo,
+    pub recommended: PlaytimeGrupo,
+    pub not_recommended: PlaytimeGrupo,
+}
+
+// Estructura auxiliar de 'PlaytimeJuego'.
+/// Estructura que representa el tiempo de juego de un grupo de reviews, al escribir la
+/// review y en total.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PlaytimeGrupo {
+    pub at_review: DistribucionPlaytime,
+    pub forever: DistribucionPlaytime,
+}
+
+// Estructura auxiliar de 'PlaytimeGrupo'.
+/// Estructura que representa una distribución de tiempos de juego, en minutos, con la
+/// cantidad de valores, su promedio y sus percentiles 10, 50 y 90 (si hay algún valor).
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DistribucionPlaytime {
+    pub count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mean: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p10: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub median: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p90: Option<f64>,
+}
+
+// Estructura auxiliar de 'TopGame'.
+/// Estructura que representa las reviews de un juego escritas durante su acceso anticipado
+/// y después de su lanzamiento, y la diferencia entre ambas fases.
+#[derive(Serialize, PartialEq)]
+pub struct FasesJuego {
+    pub early_access: FaseJuego,
+    pub post_release: FaseJuego,
+    pub difference: DiferenciaFases,
+}
+
+// Estructura auxiliar de 'FasesJuego'.
+/// Estructura que representa las reviews de un juego en una fase, con su cantidad, sus
+/// recomendaciones, el promedio de votos útiles y la mejor review de la fase.
+#[derive(Serialize, PartialEq)]
+pub struct FaseJuego {
+    pub review_count: u64,
+    pub recommendations: ResumenRecomendaciones,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_helpful_votes: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_review: Option<ReviewIdioma>,
+}
+
+// Estructura auxiliar de 'FasesJuego'.
+/// Estructura que representa la diferencia entre las reviews posteriores al lanzamiento
+/// y las del acceso anticipado de un juego (las posteriores menos las anteriores), si
+/// ambas fases tienen reviews con las que calcularla.
+#[derive(Serialize, PartialEq)]
+pub struct DiferenciaFases {
+    pub review_count: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub positive_percentage: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_helpful_votes: Option<f64>,
+}
+
+// Estructura usada para mostrar en el ".json" los juegos mejor calificados.
+/// Estructura que representa un juego en el ranking por calificación, con el nombre del
+/// juego, su `app_id`, la cantidad de reviews, sus recomendaciones y el límite inferior
+/// de Wilson de la proporción de reviews positivas.
+#[derive(Serialize, PartialEq)]
+pub struct TopRatedGame {
+    pub game: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<u64>,
+    pub review_count: u32,
+    pub recommendations: ResumenRecomendaciones,
+    pub wilson_lower_bound: f64,
 }
 
 // Estructura usada para mostrar en el ".json" los idiomas con más reviews.
 /// Estructura que representa un idioma en el top, con el nombre del idioma, la cantidad
-/// de reviews y las mejores reviews para ese idioma.
+/// de reviews, las mejores reviews para ese idioma y su review más graciosa y más comentada.
 #[derive(Serialize)]
 pub struct TopLanguage {
     pub language: String,
     pub review_count: u32,
+    pub unique_reviewers: u64,
     pub top_reviews: Vec<ReviewIdioma>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funniest_review: Option<ReviewGraciosa>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub most_discussed_review: Option<ReviewComentada>,
 }
 
-// Estructura auxiliar de 'TopLanguage'.
-/// Estructura que representa una review de un idioma en el top, con el texto de la review
-/// y el número de votos.
-#[derive(Serialize)]
+// Estructura auxiliar de 'TopLanguage' y 'FaseJuego'.
+/// Estructura que representa una review de un idioma en el top (o la mejor de una fase de
+/// un juego), con el texto de la review y el número de votos (o su valor en la métrica del
+/// ranking).
+#[derive(Serialize, PartialEq)]
 pub struct ReviewIdioma {
     pub review: String,
-    pub votes: u32,
+    pub votes: ValorMetrica,
+}
+
+// Estructura usada para mostrar en el ".json" las reviews por canal de compra.
+/// Estructura que representa las reviews por canal de compra de los juegos de `top_games`
+/// y los juegos cuyas reviews de copias gratuitas son significativamente más positivas
+/// que las de copias compradas, con el nivel de confianza, el mínimo de reviews por
+/// grupo de la comparación y la cantidad de juegos comparados.
+#[derive(Serialize)]
+pub struct PurchaseChannels {
+    pub confidence: f64,
+    pub min_reviews_per_channel: u64,
+    pub tested_games: usize,
+    pub games: Vec<CanalesJuego>,
+    pub biased_games: Vec<CanalesJuego>,
+}
+
+// Estructura auxiliar de 'PurchaseChannels'.
+/// Estructura que representa las reviews de un juego por canal de compra, con el nombre
+/// del juego, su `app_id` y la comparación de las copias gratuitas con las compradas (en
+/// Steam o con una clave), si ambos grupos tienen suficientes reviews.
+#[derive(Serialize, PartialEq)]
+pub struct CanalesJuego {
+    pub game: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<u64>,
+    pub steam_purchase: CanalJuego,
+    pub key_activation: CanalJuego,
+    pub free_copy: CanalJuego,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_copy_bias: Option<SesgoCopiasGratuitas>,
+}
+
+// Estructura auxiliar de 'CanalesJuego'.
+/// Estructura que representa las reviews de un juego obtenidas por un canal, con su
+/// cantidad y sus recomendaciones.
+#[derive(Serialize, PartialEq)]
+pub struct CanalJuego {
+    pub review_count: u64,
+    pub recommendations: ResumenRecomendaciones,
+}
+
+// Estructura auxiliar de 'CanalesJuego'.
+/// Estructura que representa la comparación de las reviews de copias gratuitas de un
+/// juego con las de copias compradas, con la diferencia de porcentaje de positivas, el
+/// valor p de la prueba exacta de Fisher, el valor p ajustado por la cantidad de juegos
+/// comparados y si la diferencia es significativa.
+#[derive(Serialize, PartialEq)]
+pub struct SesgoCopiasGratuitas {
+    pub positive_percentage_difference: f64,
+    pub p_value: f64,
+    pub adjusted_p_value: f64,
+    pub significant: bool,
+}
+
+// Estructura usada para mostrar en el ".json" la línea de tiempo de las reviews.
+/// Estructura que representa la línea de tiempo de los juegos de `top_games` y de los
+/// idiomas de `top_languages`, con la duración de sus períodos.
+#[derive(Serialize)]
+pub struct Timeline {
+    pub period: &'static str,
+    pub games: Vec<TimelineJuego>,
+    pub languages: Vec<TimelineIdioma>,
+}
+
+// Estructura auxiliar de 'Timeline'.
+/// Estructura que representa la línea de tiempo de un juego, con el nombre del juego, su
+/// `app_id` y sus períodos.
+#[derive(Serialize)]
+pub struct TimelineJuego {
+    pub game: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<u64>,
+    pub periods: Vec<PeriodoTimeline>,
+}
+
+// Estructura auxiliar de 'Timeline'.
+/// Estructura que representa la línea de tiempo de un idioma, con el nombre del idioma y
+/// sus períodos.
+#[derive(Serialize)]
+pub struct TimelineIdioma {
+    pub language: String,
+    pub periods: Vec<PeriodoTimeline>,
+}
+
+// Estructura auxiliar de 'TimelineJuego' y 'TimelineIdioma'.
+/// Estructura que representa un período de una línea de tiempo, con su inicio, la
+/// cantidad de reviews creadas en él y sus recomendaciones.
+#[derive(Serialize)]
+pub struct PeriodoTimeline {
+    pub start: String,
+    pub review_count: u64,
+    pub recommendations: ResumenRecomendaciones,
+}
+
+// Estructura usada para mostrar en el ".json" el resultado de la ingesta de un archivo.
+/// Estructura que representa la ingesta de un archivo, con las filas leídas, aceptadas
+/// y rechazadas, el detalle de los rechazos por motivo y las filas aceptadas con algún
+/// valor inválido en una columna opcional.
+#[derive(Serialize)]
+pub struct IngestaArchivo {
+    pub file: String,
+    pub rows_read: u64,
+    pub rows_accepted: u64,
+    pub rows_rejected: u64,
+    pub rows_duplicated: u64,
+    pub rejections: Vec<RechazoArchivo>,
+    pub invalid_fields: FilasArchivo,
+}
+
+// Estructura auxiliar de 'IngestaArchivo'.
+/// Estructura que representa las filas rechazadas por un motivo, con la cantidad y
+/// las primeras líneas rechazadas.
+#[derive(Serialize)]
+pub struct RechazoArchivo {
+    pub reason: &'static str,
+    pub count: u64,
+    pub first_lines: Vec<u64>,
+}
+
+// Estructura auxiliar de 'IngestaArchivo'.
+/// Estructura que representa un grupo de filas de un archivo, con la cantidad y las
+/// primeras líneas del grupo.
+#[derive(Serialize)]
+pub struct FilasArchivo {
+    pub count: u64,
+    pub first_lines: Vec<u64>,
 }
 
 // Función que hace el filtrado final y la serialización para luego escribir el ".json".
 ///
 /// # Parámetros
 /// - `est`: Estadísticas globales que se utilizarán para filtrar y serializar los datos.
+/// - `procesamiento`: Opciones con las que se procesaron los datos, con los tamaños de
+///   los rankings.
+/// - `opciones`: Opciones de la salida, con los parámetros del ranking por calificación.
 ///
 /// # Retorna
 /// - Devuelve un objeto de tipo `EstadisticasGlobalesSerializable` con los datos filtrados y serializados.
-pub fn filtrar_top3(est: &EstadisticasGlobales) -> EstadisticasGlobalesSerializable {
-    let juegos = filtrar_top_juegos_con_reviews(est);
-    let idiomas_serializables = filtrar_idiomas_serializables(est);
+pub fn filtrar_tops(
+    est: &EstadisticasGlobales,
+    procesamiento: &OpcionesProcesamiento,
+    opciones: &OpcionesSalida,
+) -> EstadisticasGlobalesSerializable {
+    let limites = &procesamiento.limites;
+    let sesgos: HashMap<_, _> = evaluar_sesgos(
+        est.juegos
+            .iter()
+            .map(|(juego, info)| (juego, &info.canales_compra)),
+        &opciones.calificacion,
+    )
+    .into_iter()
+    .collect();
+    let juegos = filtrar_top_juegos_con_reviews(est, limites, &sesgos);
+    let juegos_calificados = filtrar_juegos_calificados(est, limites, &opciones.calificacion);
+    let juegos_sesgados = filtrar_juegos_sesgados(est, &sesgos);
+    let idiomas_serializables = filtrar_idiomas_serializables(est, limites);
     let top_idiomas = obtener_top_reviews_por_idioma(&idiomas_serializables);
 
     EstadisticasGlobalesSerializable {
         juegos,
+        juegos_calificados,
+        juegos_sesgados,
+        juegos_comparados_por_canal: sesgos.len(),
         idiomas: Some(idiomas_serializables),
         top_idiomas,
+        autores_distintos: est.autores.estimacion(),
+        error_relativo_autores: est.autores.error_relativo(),
     }
 }
 
 // Función que filtra los juegos con más cantidad de reviews.
 ///
+/// Los juegos se ordenan por cantidad de reviews (de mayor a menor) y, ante un empate,
+/// por nombre y luego por `app_id`, para que el resultado no dependa del procesamiento.
+///
 /// # Parámetros
 /// - `est`: Estadísticas globales que contienen la información de los juegos.
+/// - `limites`: Tamaños de los rankings (cantidad de juegos y de idiomas por juego).
+/// - `sesgos`: Comparación de las copias gratuitas con las compradas de cada juego
+///   comparado.
 ///
 /// # Retorna
-/// - Devuelve un `HashMap` con los juegos filtrados y serializados.
+/// - Devuelve un `Vec` con los juegos filtrados y serializados.
 fn filtrar_top_juegos_con_reviews(
     est: &EstadisticasGlobales,
-) -> HashMap<String, InfoJuegoSerializable> {
-    let mut juegos_vec: Vec<_> = est.juegos.iter().collect();
-    juegos_vec.sort_by_key(|(_, info)| std::cmp::Reverse(info.cantidad_total));
-    juegos_vec.truncate(MAX_TOP_JUEGOS);
+    limites: &LimitesRanking,
+    sesgos: &HashMap<&ClaveJuego, SesgoGratis>,
+) -> Vec<InfoJuegoSerializable> {
+    let mut juegos_vec: Vec<_> = est
+        .juegos
+        .iter()
+        .map(|(juego, info)| (juego, info, info.nombre_principal()))
+        .collect();
+    juegos_vec.sort_by_key(|(juego, info, nombre)| (Reverse(info.cantidad_total), *nombre, *juego));
+    juegos_vec.truncate(limites.top_juegos.capacidad());
     juegos_vec
         .into_iter()
-        .map(|(juego, info)| {
+        .map(|(juego, info, nombre)| {
             let mut idiomas_vec: Vec<_> = info.por_idioma.iter().collect();
-            idiomas_vec
-                .sort_by_key(|(idioma, count)| (std::cmp::Reverse(**count), (*idioma).clone()));
-            idiomas_vec.truncate(MAX_TOP_REVIEWS_JUEGOS);
+            ordenar_por_cantidad(&mut idiomas_vec, |(idioma, count)| {
+                (**count, idioma.as_ref())
+            });
+            idiomas_vec.truncate(limites.idiomas_por_juego.capacidad());
             let por_idioma = idiomas_vec
                 .iter()
-                .map(|(idioma, count)| ((*idioma).clone(), **count))
+                .map(|(idioma, count)| (idioma.to_string(), **count))
                 .collect();
 
-            let mejores_reviews = idiomas_vec
+            let idiomas: Vec<_> = idiomas_vec
+                .iter()
+                .map(|(idioma, _)| idioma.as_ref())
+                .collect();
+
+            let recomendaciones_por_idioma = idiomas_vec
                 .iter()
                 .filter_map(|(idioma, _)| {
-                    info.mejores_reviews.get(*idioma).map(|(texto, votos)| {
-                        (
-                            (*idioma).clone(),
-                            MejorReview {
-                                texto: texto.clone(),
-                                votos: *votos,
-                            },
-                        )
-                    })
+                    info.recomendaciones_por_idioma
+                        .get(*idioma)
+                        .map(|conteo| (idioma.to_string(), *conteo))
                 })
                 .collect();
 
-            (
-                juego.clone(),
-                InfoJuegoSerializable {
-                    cantidad_total: info.cantidad_total,
-                    por_idioma,
-                    mejores_reviews,
-                },
-            )
+            InfoJuegoSerializable {
+                nombre: nombre.to_string(),
+                id_juego: juego.id(),
+                alias: info.alias().into_iter().map(str::to_string).collect(),
+                cantidad_total: info.cantidad_total,
+                por_idioma,
+                mejores_reviews: destacadas_por_idioma(info, RankingReseñas::Mejores, &idiomas),
+                mas_graciosas: destacadas_por_idioma(info, RankingReseñas::MasGraciosas, &idiomas),
+                mas_comentadas: destacadas_por_idioma(
+                    info,
+                    RankingReseñas::MasComentadas,
+                    &idiomas,
+                ),
+                recomendaciones: info.recomendaciones,
+                recomendaciones_por_idioma,
+                linea_de_tiempo: info.linea_de_tiempo.clone(),
+                tiempo_de_juego: resumir_tiempo_de_juego(&info.tiempo_de_juego),
+                autores_distintos: info.autores.estimacion(),
+                fases_lanzamiento: info.fases_lanzamiento.clone(),
+                canales_compra: info.canales_compra,
+                sesgo_gratis: sesgos.get(juego).copied(),
+            }
+        })
+        .collect()
+}
+
+// Función que obtiene la review destacada de un juego en cada idioma, en un ranking.
+///
+/// # Parámetros
+/// - `info`: Información del juego, con sus reviews destacadas.
+/// - `ranking`: Ranking del que se toman las reviews.
+/// - `idiomas`: Idiomas del juego que se incluyen.
+///
+/// # Retorna
+/// - Devuelve un `HashMap` con la review destacada de cada idioma que tenga alguna.
+fn destacadas_por_idioma(
+    info: &InfoJuego,
+    ranking: RankingReseñas,
+    idiomas: &[&str],
+) -> HashMap<String, MejorReview> {
+    idiomas
+        .iter()
+        .filter_map(|idioma| {
+            info.destacadas(ranking).get(*idioma).map(|reseña| {
+                (
+                    idioma.to_string(),
+                    MejorReview {
+                        texto: reseña.texto.clone(),
+                        valor: reseña.valor,
+                    },
+                )
+            })
+        })
+        .collect()
+}
+
+// Función que filtra los juegos mejor calificados.
+///
+/// Los juegos se ordenan por el límite inferior de Wilson de su proporción de reviews
+/// positivas (de mayor a menor) y, ante un empate, por nombre y luego por `app_id`. Sólo
+/// participan los juegos con, al menos, el mínimo de reviews con recomendación.
+///
+/// # Parámetros
+/// - `est`: Estadísticas globales que contienen la información de los juegos.
+/// - `limites`: Tamaños de los rankings (cantidad de juegos mejor calificados).
+/// - `calificacion`: Nivel de confianza y mínimo de reviews del ranking.
+///
+/// # Retorna
+/// - Devuelve un `Vec` con los juegos mejor calificados, del mejor al peor.
+fn filtrar_juegos_calificados(
+    est: &EstadisticasGlobales,
+    limites: &LimitesRanking,
+    calificacion: &OpcionesCalificacion,
+) -> Vec<JuegoCalificadoSerializable> {
+    let z = calificacion.valor_z();
+    let mut juegos_vec: Vec<_> = est
+        .juegos
+        .iter()
+        .filter(|(_, info)| info.recomendaciones.total() >= calificacion.minimo_reseñas)
+        .filter_map(|(juego, info)| {
+            let puntaje = info.recomendaciones.limite_inferior_wilson(z)?;
+            Some((juego, info, info.nombre_principal(), puntaje))
+        })
+        .collect();
+    juegos_vec.sort_by(
+        |(juego_a, _, nombre_a, puntaje_a), (juego_b, _, nombre_b, puntaje_b)| {
+            puntaje_b
+                .total_cmp(puntaje_a)
+                .then_with(|| (nombre_a, juego_a).cmp(&(nombre_b, juego_b)))
+        },
+    );
+    juegos_vec.truncate(limites.top_calificados.capacidad());
+    juegos_vec
+        .into_iter()
+        .map(
+            |(juego, info, nombre, puntaje)| JuegoCalificadoSerializable {
+                nombre: nombre.to_string(),
+                id_juego: juego.id(),
+                cantidad_total: info.cantidad_total,
+                recomendaciones: info.recomendaciones,
+                limite_inferior_wilson: puntaje,
+            },
+        )
+        .collect()
+}
+
+// Función que filtra los juegos con sesgo de copias gratuitas.
+///
+/// Incluye a todos los juegos cuyas reviews de copias gratuitas son significativamente
+/// más positivas que las de copias compradas, ordenados por el valor p de la diferencia
+/// (de menor a mayor) y, ante un empate, por nombre y luego por `app_id`.
+///
+/// # Parámetros
+/// - `est`: Estadísticas globales que contienen la información de los juegos.
+/// - `sesgos`: Comparación de las copias gratuitas con las compradas de cada juego
+///   comparado.
+///
+/// # Retorna
+/// - Devuelve un `Vec` con los juegos con sesgo, del más sesgado al menos sesgado.
+fn filtrar_juegos_sesgados(
+    est: &EstadisticasGlobales,
+    sesgos: &HashMap<&ClaveJuego, SesgoGratis>,
+) -> Vec<JuegoSesgadoSerializable> {
+    let mut juegos_vec: Vec<_> = sesgos
+        .iter()
+        .filter(|(_, sesgo)| sesgo.significativo)
+        .map(|(juego, sesgo)| {
+            let info = &est.juegos[*juego];
+            (*juego, info, info.nombre_principal(), sesgo)
+        })
+        .collect();
+    juegos_vec.sort_by(
+        |(juego_a, _, nombre_a, sesgo_a), (juego_b, _, nombre_b, sesgo_b)| {
+            sesgo_a
+                .valor_p
+                .total_cmp(&sesgo_b.valor_p)
+                .then_with(|| (nombre_a, juego_a).cmp(&(nombre_b, juego_b)))
+        },
+    );
+    juegos_vec
+        .into_iter()
+        .map(|(juego, info, nombre, sesgo)| JuegoSesgadoSerializable {
+            nombre: nombre.to_string(),
+            id_juego: juego.id(),
+            canales_compra: info.canales_compra,
+            sesgo_gratis: *sesgo,
         })
         .collect()
 }
@@ -179,33 +707,51 @@ fn filtrar_top_juegos_con_reviews(
 ///
 /// # Parámetros
 /// - `est`: Estadísticas globales que contienen la información de los idiomas.
+/// - `limites`: Tamaños de los rankings (cantidad de idiomas y de reseñas por idioma).
 ///
 /// # Retorna
 /// - Devuelve un `HashMap` con los idiomas filtrados y serializados.
 fn filtrar_idiomas_serializables(
     est: &EstadisticasGlobales,
+    limites: &LimitesRanking,
 ) -> HashMap<String, InfoIdiomaSerializable> {
     let mut idiomas_vec: Vec<_> = est.por_idioma.iter().collect();
-    idiomas_vec.sort_by_key(|(_, info)| std::cmp::Reverse(info.cantidad_total));
-    idiomas_vec.truncate(MAX_TOP_IDIOMAS);
+    ordenar_por_cantidad(&mut idiomas_vec, |(idioma, info)| {
+        (info.cantidad_total, idioma.as_ref())
+    });
+    idiomas_vec.truncate(limites.top_idiomas.capacidad());
     idiomas_vec
         .into_iter()
         .map(|(idioma, info)| {
             let top_reviews = info
                 .top_reviews
-                .iter()
-                .take(MAX_TOP_REVIEWS_IDIOMAS)
-                .map(|(texto, votos)| MejorReview {
-                    texto: texto.clone(),
-                    votos: *votos,
+                .ordenados()
+                .into_iter()
+                .take(limites.reviews_por_idioma.capacidad())
+                .map(|reseña| MejorReview {
+                    texto: reseña.texto.clone(),
+                    valor: reseña.valor,
                 })
                 .collect();
+            let destacada = |ranking| {
+                info.destacadas(ranking)
+                    .ordenados()
+                    .first()
+                    .map(|reseña| MejorReview {
+                        texto: reseña.texto.clone(),
+                        valor: reseña.valor,
+                    })
+            };
 
             (
-                idioma.clone(),
+                idioma.to_string(),
                 InfoIdiomaSerializable {
                     cantidad_total: info.cantidad_total,
                     top_reviews,
+                    mas_graciosa: destacada(RankingReseñas::MasGraciosas),
+                    mas_comentada: destacada(RankingReseñas::MasComentadas),
+                    linea_de_tiempo: info.linea_de_tiempo.clone(),
+                    autores_distintos: info.autores.estimacion(),
                 },
             )
         })
@@ -222,10 +768,8 @@ fn filtrar_idiomas_serializables(
 fn obtener_top_reviews_por_idioma(
     idiomas: &HashMap<String, InfoIdiomaSerializable>,
 ) -> HashMap<String, Vec<MejorReview>> {
-    let mut idiomas_vec = idiomas.iter().collect::<Vec<_>>();
-    idiomas_vec.sort_by_key(|(_, info)| std::cmp::Reverse(info.cantidad_total));
-    idiomas_vec
-        .into_iter()
+    idiomas
+        .iter()
         .map(|(idioma, info)| (idioma.clone(), info.top_reviews.clone()))
         .collect()
 }
@@ -233,24 +777,78 @@ fn obtener_top_reviews_por_idioma(
 // Trait que se encarga de crear la salida final para el ".json".
 ///
 /// # Método
-/// - `a_salida_final(padron: u32)`
-///     - Convierte las estadísticas globales serializadas en una salida final.
+/// - `a_salida_final(padron: u32, procesamiento: &OpcionesProcesamiento, opciones: &OpcionesSalida)`
+///     - Convierte las estadísticas globales serializadas en una salida final, con las
+///       mismas opciones con las que se procesaron y filtraron.
 ///
 pub trait ASalidaFinal {
-    fn a_salida_final(&self, padron: u32) -> SalidaFinal;
+    fn a_salida_final(
+        &self,
+        padron: u32,
+        procesamiento: &OpcionesProcesamiento,
+        opciones: &OpcionesSalida,
+    ) -> SalidaFinal;
 }
 
 // Método que implementa la lógica para crear la salida final.
 impl ASalidaFinal for EstadisticasGlobalesSerializable {
-    fn a_salida_final(&self, padron: u32) -> SalidaFinal {
-        let top_games = convertir_top_games(&self.juegos);
+    fn a_salida_final(
+        &self,
+        padron: u32,
+        procesamiento: &OpcionesProcesamiento,
+        opciones: &OpcionesSalida,
+    ) -> SalidaFinal {
+        let metrica = procesamiento.metrica_reviews;
+        let top_games = convertir_top_games(&self.juegos, metrica);
+        let top_rated_games = convertir_top_rated_games(&self.juegos_calificados);
         let top_languages =
             convertir_top_languages(self.idiomas.as_ref().expect(ERROR_INFORMACION_IDIOMAS));
 
         SalidaFinal {
             padron,
+            vote_overflow_policy: procesamiento.votos_desbordados.nombre(),
+            review_metric: metrica.nombre(),
+            duplicates_removed: None,
+            unique_reviewers: AutoresDistintos {
+                estimate: self.autores_distintos,
+                relative_standard_error: self
+                    .error_relativo_autores
+                    .map(|error| redondear(error, 4)),
+            },
             top_games,
+            top_rated_games,
             top_languages,
+            purchase_channels: PurchaseChannels {
+                confidence: opciones.calificacion.confianza,
+                min_reviews_per_channel: opciones.calificacion.minimo_reseñas,
+                tested_games: self.juegos_comparados_por_canal,
+                games: self
+                    .juegos
+                    .iter()
+                    .map(|info| {
+                        resumir_canales(
+                            &info.nombre,
+                            info.id_juego,
+                            &info.canales_compra,
+                            info.sesgo_gratis.as_ref(),
+                        )
+                    })
+                    .collect(),
+                biased_games: self
+                    .juegos_sesgados
+                    .iter()
+                    .map(|info| {
+                        resumir_canales(
+                            &info.nombre,
+                            info.id_juego,
+                            &info.canales_compra,
+                            Some(&info.sesgo_gratis),
+                        )
+                    })
+                    .collect(),
+            },
+            timeline: None,
+            ingestion_report: None,
         }
     }
 }
@@ -258,46 +856,305 @@ impl ASalidaFinal for EstadisticasGlobalesSerializable {
 // Función que implementa la lógica de conversión de los juegos para la salida.
 ///
 /// # Parámetros
-/// - `juegos`: Un `HashMap` con la información de los juegos.
+/// - `juegos`: Un `Vec` con la información de los juegos.
+/// - `metrica`: Métrica con la que se eligieron las mejores reviews, para mostrar el
+///   valor de los idiomas sin ninguna review con valor en ella.
 ///
 /// # Retorna
 /// - Devuelve un `Vec<TopGame>` con la información de los juegos en formato adecuado.
-fn convertir_top_games(juegos: &HashMap<String, InfoJuegoSerializable>) -> Vec<TopGame> {
+fn convertir_top_games(juegos: &[InfoJuegoSerializable], metrica: MetricaReseñas) -> Vec<TopGame> {
     let mut top_games: Vec<TopGame> = juegos
         .iter()
-        .map(|(juego, info)| {
+        .map(|info| {
             let mut languages: Vec<IdiomaPorJuego> = info
                 .por_idioma
                 .iter()
                 .map(|(idioma, count)| {
-                    let (texto, votos) = info
+                    let (texto, valor) = info
                         .mejores_reviews
                         .get(idioma)
-                        .map(|mr| (mr.texto.clone(), mr.votos))
-                        .unwrap_or_else(|| ("".to_string(), 0));
+                        .map(|mr| (mr.texto.clone(), mr.valor))
+                        .unwrap_or_else(|| ("".to_string(), metrica.valor_vacio()));
 
                     IdiomaPorJuego {
                         language: idioma.clone(),
                         review_count: *count as u32,
+                        recommendations: resumir_recomendaciones(
+                            info.recomendaciones_por_idioma
+                                .get(idioma)
+                                .copied()
+                                .unwrap_or_default(),
+                        ),
                         top_review: texto,
-                        top_review_votes: votos,
+                        top_review_votes: valor,
+                        funniest_review: info.mas_graciosas.get(idioma).map(a_review_graciosa),
+                        most_discussed_review: info
+                            .mas_comentadas
+                            .get(idioma)
+                            .map(a_review_comentada),
                     }
                 })
                 .collect();
 
-            languages.sort_by(|a, b| b.review_count.cmp(&a.review_count));
+            ordenar_por_cantidad(&mut languages, |l| (l.review_count, l.language.as_str()));
             TopGame {
-                game: juego.clone(),
+                game: info.nombre.clone(),
+                app_id: info.id_juego,
+                aliases: info.alias.clone(),
                 review_count: info.cantidad_total as u32,
+                unique_reviewers: info.autores_distintos,
+                recommendations: resumir_recomendaciones(info.recomendaciones),
+                playtime: info.tiempo_de_juego.clone(),
+                release_phases: resumir_fases(&info.fases_lanzamiento),
                 languages,
             }
         })
         .collect();
 
-    top_games.sort_by(|a, b| b.review_count.cmp(&a.review_count));
+    top_games.sort_by(|a, b| {
+        (Reverse(a.review_count), &a.game, a.app_id).cmp(&(
+            Reverse(b.review_count),
+            &b.game,
+            b.app_id,
+        ))
+    });
     top_games
 }
 
+// Función que implementa la lógica de conversión de los juegos mejor calificados para
+// la salida.
+///
+/// # Parámetros
+/// - `juegos`: Un `Vec` con la información de los juegos, ya ordenados.
+///
+/// # Retorna
+/// - Devuelve un `Vec<TopRatedGame>` con la información de los juegos en formato adecuado,
+///   con el límite inferior de Wilson redondeado a cuatro decimales.
+fn convertir_top_rated_games(juegos: &[JuegoCalificadoSerializable]) -> Vec<TopRatedGame> {
+    juegos
+        .iter()
+        .map(|info| TopRatedGame {
+            game: info.nombre.clone(),
+            app_id: info.id_juego,
+            review_count: info.cantidad_total as u32,
+            recommendations: resumir_recomendaciones(info.recomendaciones),
+            wilson_lower_bound: redondear(info.limite_inferior_wilson, 4),
+        })
+        .collect()
+}
+
+// Función que arma la línea de tiempo de la salida.
+///
+/// Incluye a los juegos y a los idiomas de los rankings, en el mismo orden que en
+/// `top_games` y `top_languages`.
+///
+/// # Parámetros
+/// - `est`: Estadísticas ya filtradas, con la línea de tiempo de cada juego e idioma.
+/// - `periodo`: Duración de los períodos con los que se armó la línea de tiempo.
+///
+/// # Retorna
+/// - Devuelve un `Timeline` con los períodos de cada juego e idioma, del más antiguo al
+///   más reciente.
+pub fn convertir_linea_de_tiempo(
+    est: &EstadisticasGlobalesSerializable,
+    periodo: Periodo,
+) -> Timeline {
+    let games = est
+        .juegos
+        .iter()
+        .map(|info| TimelineJuego {
+            game: info.nombre.clone(),
+            app_id: info.id_juego,
+            periods: convertir_periodos(&info.linea_de_tiempo, periodo),
+        })
+        .collect();
+
+    let mut idiomas: Vec<_> = est.idiomas.iter().flatten().collect();
+    ordenar_por_cantidad(&mut idiomas, |(idioma, info)| {
+        (info.cantidad_total, idioma.as_str())
+    });
+    let languages = idiomas
+        .into_iter()
+        .map(|(idioma, info)| TimelineIdioma {
+            language: idioma.clone(),
+            periods: convertir_periodos(&info.linea_de_tiempo, periodo),
+        })
+        .collect();
+
+    Timeline {
+        period: periodo.nombre(),
+        games,
+        languages,
+    }
+}
+
+// Función que convierte los períodos de una línea de tiempo para la salida.
+///
+/// # Parámetros
+/// - `linea_de_tiempo`: Línea de tiempo de un juego o idioma.
+/// - `periodo`: Duración de los períodos, usada para etiquetarlos.
+///
+/// # Retorna
+/// - Devuelve un `Vec<PeriodoTimeline>` con los períodos, del más antiguo al más reciente.
+fn convertir_periodos(linea_de_tiempo: &LineaDeTiempo, periodo: Periodo) -> Vec<PeriodoTimeline> {
+    linea_de_tiempo
+        .periodos()
+        .map(|(inicio, conteo)| PeriodoTimeline {
+            start: periodo.etiqueta(inicio),
+            review_count: conteo.cantidad,
+            recommendations: resumir_recomendaciones(conteo.recomendaciones),
+        })
+        .collect()
+}
+
+// Función que resume las recomendaciones de un juego para la salida.
+///
+/// # Parámetros
+/// - `conteo`: Reviews positivas y negativas del juego (o del juego en un idioma).
+///
+/// # Retorna
+/// - Devuelve un `ResumenRecomendaciones` con el porcentaje de positivas redondeado a dos
+///   decimales y la calificación de Steam.
+fn resumir_recomendaciones(conteo: ConteoRecomendaciones) -> ResumenRecomendaciones {
+    ResumenRecomendaciones {
+        positive: conteo.positivas,
+        negative: conteo.negativas,
+        positive_percentage: conteo
+            .porcentaje_positivo()
+            .map(|porcentaje| redondear(porcentaje, 2)),
+        rating: conteo
+            .calificacion()
+            .map(|calificacion| calificacion.nombre()),
+    }
+}
+
+// Función que resume las reviews de las fases de lanzamiento de un juego para la salida.
+///
+/// # Parámetros
+/// - `fases`: Reviews del juego escritas durante el acceso anticipado y después del
+///   lanzamiento.
+///
+/// # Retorna
+/// - Devuelve un `FasesJuego` con cada fase y la diferencia entre ambas, con los
+///   porcentajes y los promedios redondeados a dos decimales.
+fn resumir_fases(fases: &FasesLanzamiento) -> FasesJuego {
+    let resumir_fase = |fase: &ReseñasFase| FaseJuego {
+        review_count: fase.cantidad,
+        recommendations: resumir_recomendaciones(fase.recomendaciones),
+        average_helpful_votes: fase.promedio_votos().map(|promedio| redondear(promedio, 2)),
+        best_review: fase.mejor.as_ref().map(|reseña| ReviewIdioma {
+            review: reseña.texto.clone(),
+            votes: reseña.valor,
+        }),
+    };
+    let (anticipado, lanzamiento) = (&fases.acceso_anticipado, &fases.lanzamiento);
+    let diferencia = |valor: fn(&ReseñasFase) -> Option<f64>| {
+        Some(redondear(valor(lanzamiento)? - valor(anticipado)?, 2))
+    };
+
+    FasesJuego {
+        early_access: resumir_fase(anticipado),
+        post_release: resumir_fase(lanzamiento),
+        difference: DiferenciaFases {
+            review_count: lanzamiento.cantidad as i64 - anticipado.cantidad as i64,
+            positive_percentage: diferencia(|fase| fase.recomendaciones.porcentaje_positivo()),
+            average_helpful_votes: diferencia(ReseñasFase::promedio_votos),
+        },
+    }
+}
+
+// Función que resume las reviews por canal de compra de un juego para la salida.
+///
+/// # Parámetros
+/// - `nombre`: Nombre del juego.
+/// - `id_juego`: `app_id` del juego, si sus reviews lo tienen.
+/// - `canales`: Reviews del juego por canal de compra.
+/// - `sesgo`: Comparación de las copias gratuitas con las compradas, si se comparó el
+///   juego.
+///
+/// # Retorna
+/// - Devuelve un `CanalesJuego` con las reviews de cada canal y la comparación de las
+///   copias gratuitas con las compradas, con la diferencia de porcentajes redondeada a
+///   dos decimales y los valores p sin redondear.
+fn resumir_canales(
+    nombre: &str,
+    id_juego: Option<u64>,
+    canales: &CanalesCompra,
+    sesgo: Option<&SesgoGratis>,
+) -> CanalesJuego {
+    let resumir_canal = |canal: &ConteoCanal| CanalJuego {
+        review_count: canal.cantidad,
+        recommendations: resumir_recomendaciones(canal.recomendaciones),
+    };
+
+    CanalesJuego {
+        game: nombre.to_string(),
+        app_id: id_juego,
+        steam_purchase: resumir_canal(&canales.steam),
+        key_activation: resumir_canal(&canales.clave),
+        free_copy: resumir_canal(&canales.gratis),
+        free_copy_bias: sesgo.map(|sesgo| SesgoCopiasGratuitas {
+            positive_percentage_difference: redondear(sesgo.diferencia_porcentual, 2),
+            p_value: sesgo.valor_p,
+            adjusted_p_value: sesgo.valor_p_ajustado,
+            significant: sesgo.significativo,
+        }),
+    }
+}
+
+// Función que resume el tiempo de juego de un juego para la salida.
+///
+/// # Parámetros
+/// - `tiempo`: Distribuciones del tiempo de juego de las reviews del juego.
+///
+/// # Retorna
+/// - Devuelve un `PlaytimeJuego` con el promedio y los percentiles de cada distribución,
+///   redondeados a dos decimales.
+fn resumir_tiempo_de_juego(tiempo: &TiempoDeJuegoPorRecomendacion) -> PlaytimeJuego {
+    let resumir_grupo = |grupo: &TiempoDeJuego| PlaytimeGrupo {
+        at_review: resumir_distribucion(&grupo.al_reseñar),
+        forever: resumir_distribucion(&grupo.total),
+    };
+
+    PlaytimeJuego {
+        all: resumir_grupo(&tiempo.todas),
+        recommended: resumir_grupo(&tiempo.recomendadas),
+        not_recommended: resumir_grupo(&tiempo.no_recomendadas),
+    }
+}
+
+// Función que resume una distribución de tiempos de juego para la salida.
+///
+/// # Parámetros
+/// - `boceto`: Boceto de cuantiles con los tiempos de juego, en minutos.
+///
+/// # Retorna
+/// - Devuelve un `DistribucionPlaytime` con la cantidad de valores, el promedio exacto y
+///   los percentiles estimados (con un error relativo menor al 1%).
+fn resumir_distribucion(boceto: &BocetoCuantiles) -> DistribucionPlaytime {
+    let cuantil = |q| boceto.cuantil(q).map(|valor| redondear(valor, 2));
+    DistribucionPlaytime {
+        count: boceto.cantidad(),
+        mean: boceto.promedio().map(|promedio| redondear(promedio, 2)),
+        p10: cuantil(CUANTIL_P10),
+        median: cuantil(CUANTIL_MEDIANA),
+        p90: cuantil(CUANTIL_P90),
+    }
+}
+
+// Función que redondea un valor decimal para la salida.
+///
+/// # Parámetros
+/// - `valor`: Valor a redondear.
+/// - `decimales`: Cantidad de decimales que se conservan.
+///
+/// # Retorna
+/// - Devuelve el valor redondeado.
+fn redondear(valor: f64, decimales: i32) -> f64 {
+    let escala = 10_f64.powi(decimales);
+    (valor * escala).round() / escala
+}
+
 // Función que implementa la lógica de conversión de los idiomas para la salida.
 ///
 /// # Parámetros
@@ -311,17 +1168,100 @@ fn convertir_top_languages(idiomas: &HashMap<String, InfoIdiomaSerializable>) ->
         .map(|(idioma, info)| TopLanguage {
             language: idioma.clone(),
             review_count: info.cantidad_total as u32,
+            unique_reviewers: info.autores_distintos,
             top_reviews: info
                 .top_reviews
                 .iter()
                 .map(|mr| ReviewIdioma {
                     review: mr.texto.clone(),
-                    votes: mr.votos,
+                    votes: mr.valor,
                 })
                 .collect(),
+            funniest_review: info.mas_graciosa.as_ref().map(a_review_graciosa),
+            most_discussed_review: info.mas_comentada.as_ref().map(a_review_comentada),
         })
         .collect();
 
-    top_languages.sort_by(|a, b| b.review_count.cmp(&a.review_count));
+    ordenar_por_cantidad(&mut top_languages, |l| {
+        (l.review_count, l.language.as_str())
+    });
     top_languages
 }
+
+// Función que convierte la review más graciosa de un juego o idioma para la salida.
+///
+/// # Parámetros
+/// - `review`: La review con más votos graciosos.
+///
+/// # Retorna
+/// - Devuelve un `ReviewGraciosa` con el texto y los votos graciosos de la review.
+fn a_review_graciosa(review: &MejorReview) -> ReviewGraciosa {
+    ReviewGraciosa {
+        review: review.texto.clone(),
+        funny_votes: review.valor,
+    }
+}
+
+// Función que convierte la review más comentada de un juego o idioma para la salida.
+///
+/// # Parámetros
+/// - `review`: La review con más comentarios.
+///
+/// # Retorna
+/// - Devuelve un `ReviewComentada` con el texto y la cantidad de comentarios de la review.
+fn a_review_comentada(review: &MejorReview) -> ReviewComentada {
+    ReviewComentada {
+        review: review.texto.clone(),
+        comments: review.valor,
+    }
+}
+
+// Función que ordena elementos por cantidad y nombre.
+///
+/// Es el orden usado en todos los rankings por cantidad: de mayor a menor cantidad y,
+/// ante un empate, por nombre en orden alfabético.
+///
+/// # Parámetros
+/// - `elementos`: Los elementos a ordenar.
+/// - `clave`: Función que devuelve la cantidad y el nombre de un elemento.
+fn ordenar_por_cantidad<T, C: Ord>(elementos: &mut [T], clave: impl Fn(&T) -> (C, &str)) {
+    elementos.sort_by(|a, b| {
+        let (cantidad_a, nombre_a) = clave(a);
+        let (cantidad_b, nombre_b) = clave(b);
+        cantidad_b.cmp(&cantidad_a).then(nombre_a.cmp(nombre_b))
+    });
+}
+
+// Función que implementa la lógica de conversión del reporte de ingesta para la salida.
+///
+/// # Parámetros
+/// - `reporte`: El reporte de ingesta de todos los archivos procesados.
+///
+/// # Retorna
+/// - Devuelve un `Vec<IngestaArchivo>` ordenado por ruta de archivo.
+pub fn convertir_reporte_ingesta(reporte: &ReporteIngesta) -> Vec<IngestaArchivo> {
+    reporte
+        .archivos
+        .iter()
+        .map(|(ruta, archivo)| IngestaArchivo {
+            file: ruta.clone(),
+            rows_read: archivo.filas_leidas,
+            rows_accepted: archivo.filas_aceptadas,
+            rows_rejected: archivo.filas_rechazadas(),
+            rows_duplicated: archivo.filas_duplicadas,
+            rejections: archivo
+                .rechazos
+                .iter()
+                .map(|(motivo, rechazos)| RechazoArchivo {
+                    reason: motivo.nombre(),
+                    count: rechazos.cantidad,
+                    first_lines: rechazos.primeras_lineas.clone(),
+                })
+                .collect(),
+            invalid_fields: FilasArchivo {
+                count: archivo.campos_invalidos.cantidad,
+                first_lines: archivo.campos_invalidos.primeras_lineas.clone(),
+            },
+        })
+        .collect()
+}
@@ -0,0 +1,199 @@
+//! Este módulo contiene el estimador 'HyperLogLog' usado para contar, en forma
+//! aproximada, los autores distintos de las reseñas sin guardar todos sus identificadores.
+
+// Imports de crates externas.
+use std::borrow::Cow;
+
+// Constantes.
+pub const PRECISION_MINIMA: u8 = 4;
+pub const PRECISION_MAXIMA: u8 = 16;
+pub const PRECISION_POR_DEFECTO: u8 = 12;
+const BITS_HASH: u32 = u64::BITS;
+const FACTOR_ERROR_ESTANDAR: f64 = 1.04;
+// Debajo de este múltiplo de la cantidad de registros, la estimación se corrige con el
+// conteo lineal de los registros vacíos.
+const UMBRAL_CONTEO_LINEAL: f64 = 2.5;
+
+// Mensajes.
+const ERROR_PRECISIONES_DISTINTAS: &str =
+    "Sólo se pueden fusionar estimadores de la misma precisión";
+
+/// Estimador 'HyperLogLog' de la cantidad de valores distintos.
+///
+/// Cada valor se mezcla con un 'hash' fijo (no aleatorio, para que la estimación sea la
+/// misma en todas las ejecuciones): sus primeros `precision` bits eligen uno de los
+/// `2^precision` registros, que guarda la mayor posición del primer bit en 1 del resto
+/// del 'hash'. La fusión de dos estimadores se queda con el máximo de cada registro, por
+/// lo que es exacta e independiente del orden, y el error estándar relativo de la
+/// estimación es `1.04 / √(2^precision)`.
+///
+/// Un estimador vacío no ocupa memoria, y mientras tenga pocos registros no nulos sólo
+/// guarda esos (ver `Registros`).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HyperLogLog {
+    precision: u8,
+    registros: Registros,
+}
+
+/// Registros de un estimador. Los dispersos se pasan a densos apenas su cantidad supera
+/// `2^precision / 8` (la mitad de la memoria de los densos), por lo que la
+/// representación depende sólo de los registros y no del orden en que se llenaron.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Registros {
+    /// Índice y valor de los registros no nulos, ordenados por índice.
+    Dispersos(Vec<(u16, u8)>),
+    /// Valor de todos los registros.
+    Densos(Vec<u8>),
+}
+
+impl Default for Registros {
+    fn default() -> Self {
+        Registros::Dispersos(Vec::new())
+    }
+}
+
+impl HyperLogLog {
+    /// Registra un valor, usando `2^precision` registros si el estimador está vacío.
+    ///
+    /// Todos los estimadores que se fusionan deben usar la misma precisión.
+    pub fn registrar(&mut self, precision: u8, valor: u64) {
+        if self.precision == 0 {
+            self.precision = precision;
+        }
+
+        let precision = u32::from(self.precision);
+        let hash = mezclar(valor);
+        let indice = (hash >> (BITS_HASH - precision)) as usize;
+        let resto = hash << precision;
+        let posicion = (resto.leading_zeros() + 1).min(BITS_HASH - precision + 1) as u8;
+        self.actualizar(indice, posicion);
+    }
+
+    /// Estimación de la cantidad de valores distintos registrados.
+    pub fn estimacion(&self) -> u64 {
+        if self.precision == 0 {
+            return 0;
+        }
+
+        let registros = self.densos();
+        let cantidad_registros = registros.len() as f64;
+        let suma: f64 = registros
+            .iter()
+            .map(|registro| 2_f64.powi(-i32::from(*registro)))
+            .sum();
+        let estimacion = alfa(registros.len()) * cantidad_registros * cantidad_registros / suma;
+
+        let vacios = registros.iter().filter(|registro| **registro == 0).count();
+        let estimacion = if estimacion <= UMBRAL_CONTEO_LINEAL * cantidad_registros && vacios > 0 {
+            cantidad_registros * (cantidad_registros / vacios as f64).ln()
+        } else {
+            estimacion
+        };
+
+        estimacion.round() as u64
+    }
+
+    /// Error estándar relativo de la estimación, si se registró algún valor.
+    pub fn error_relativo(&self) -> Option<f64> {
+        (self.precision != 0)
+            .then(|| FACTOR_ERROR_ESTANDAR / (self.cantidad_registros() as f64).sqrt())
+    }
+
+    /// Fusiona los registros de `self` en `destino`. Entra en pánico si ambos tienen
+    /// registros y sus precisiones son distintas.
+    pub fn merge_into(self, destino: &mut HyperLogLog) {
+        if self.precision == 0 {
+            return;
+        }
+        if destino.precision == 0 {
+            *destino = self;
+            return;
+        }
+
+        assert_eq!(
+            self.precision, destino.precision,
+            "{}",
+            ERROR_PRECISIONES_DISTINTAS
+        );
+        match self.registros {
+            Registros::Dispersos(propios) => {
+                for (indice, posicion) in propios {
+                    destino.actualizar(usize::from(indice), posicion);
+                }
+            }
+            Registros::Densos(propios) => {
+                destino.densificar();
+                if let Registros::Densos(registros) = &mut destino.registros {
+                    for (registro, propio) in registros.iter_mut().zip(propios) {
+                        *registro = (*registro).max(propio);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Guarda en el registro `indice` el máximo entre su valor y `posicion`.
+    fn actualizar(&mut self, indice: usize, posicion: u8) {
+        let limite_dispersos = self.cantidad_registros() / 8;
+        match &mut self.registros {
+            Registros::Densos(registros) => {
+                registros[indice] = registros[indice].max(posicion);
+            }
+            Registros::Dispersos(registros) => {
+                match registros.binary_search_by_key(&(indice as u16), |(i, _)| *i) {
+                    Ok(i) => registros[i].1 = registros[i].1.max(posicion),
+                    Err(i) => registros.insert(i, (indice as u16, posicion)),
+                }
+                if registros.len() > limite_dispersos {
+                    self.densificar();
+                }
+            }
+        }
+    }
+
+    /// Pasa los registros dispersos a densos.
+    fn densificar(&mut self) {
+        if let Registros::Dispersos(_) = self.registros {
+            let densos = self.densos().into_owned();
+            self.registros = Registros::Densos(densos);
+        }
+    }
+
+    /// Valor de todos los registros, reservándolos si son dispersos.
+    fn densos(&self) -> Cow<'_, [u8]> {
+        match &self.registros {
+            Registros::Densos(registros) => Cow::Borrowed(registros),
+            Registros::Dispersos(dispersos) => {
+                let mut registros = vec![0; self.cantidad_registros()];
+                for (indice, posicion) in dispersos {
+                    registros[usize::from(*indice)] = *posicion;
+                }
+                Cow::Owned(registros)
+            }
+        }
+    }
+
+    /// Cantidad de registros (`2^precision`) del estimador.
+    fn cantidad_registros(&self) -> usize {
+        1 << self.precision
+    }
+}
+
+/// Constante de corrección del sesgo de la estimación, según la cantidad de registros.
+fn alfa(cantidad_registros: usize) -> f64 {
+    match cantidad_registros {
+        16 => 0.673,
+        32 => 0.697,
+        64 => 0.709,
+        _ => 0.7213 / (1.0 + 1.079 / cantidad_registros as f64),
+    }
+}
+
+/// Mezcla los bits de un valor con el finalizador de 'SplitMix64', para que valores
+/// consecutivos (como los `steamid`) queden repartidos uniformemente.
+fn mezclar(valor: u64) -> u64 {
+    let mut x = valor.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
@@ -0,0 +1,66 @@
+//! Este módulo contiene el internador de cadenas usado para los nombres de los juegos
+//! y los idiomas, que se repiten en millones de reseñas.
+
+// Imports de crates externas.
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// Internador de cadenas compartido entre todos los hilos trabajadores.
+///
+/// Guarda una única copia de cada cadena, de modo que todas las estadísticas comparten la
+/// misma reserva de memoria para un mismo nombre de juego o idioma, y copiar una clave
+/// cuesta sólo incrementar un contador de referencias.
+#[derive(Debug, Default)]
+pub struct Internador {
+    cadenas: Mutex<HashSet<Arc<str>>>,
+}
+
+/// Cache local de un `Internador`, usada mientras se procesa un chunk.
+///
+/// Evita tomar el `Mutex` del internador compartido en cada reseña: sólo se consulta al
+/// internador la primera vez que aparece una cadena en el chunk.
+#[derive(Debug)]
+pub struct CacheInternado<'a> {
+    compartido: &'a Internador,
+    locales: HashSet<Arc<str>>,
+}
+
+impl Internador {
+    /// Devuelve la copia compartida de `cadena`, creándola si todavía no existe.
+    pub fn internar(&self, cadena: &str) -> Arc<str> {
+        let mut cadenas = self
+            .cadenas
+            .lock()
+            .unwrap_or_else(|envenenado| envenenado.into_inner());
+
+        match cadenas.get(cadena) {
+            Some(existente) => Arc::clone(existente),
+            None => {
+                let nueva: Arc<str> = Arc::from(cadena);
+                cadenas.insert(Arc::clone(&nueva));
+                nueva
+            }
+        }
+    }
+
+    /// Crea una cache local vacía de este internador.
+    pub fn cache(&self) -> CacheInternado<'_> {
+        CacheInternado {
+            compartido: self,
+            locales: HashSet::new(),
+        }
+    }
+}
+
+impl CacheInternado<'_> {
+    /// Devuelve la copia compartida de `cadena`, consultando primero la cache local.
+    pub fn internar(&mut self, cadena: &str) -> Arc<str> {
+        if let Some(existente) = self.locales.get(cadena) {
+            return Arc::clone(existente);
+        }
+
+        let nueva = self.compartido.internar(cadena);
+        self.locales.insert(Arc::clone(&nueva));
+        nueva
+    }
+}
@@ -0,0 +1,65 @@
+//! Este módulo contiene el semáforo que limita la cantidad de chunks en vuelo
+//! entre los hilos productores y los hilos trabajadores.
+
+// Imports de crates externas.
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Semáforo contador compartido entre productores y trabajadores.
+///
+/// Cada chunk leído toma un permiso, y lo devuelve recién cuando el trabajador
+/// termina de procesarlo. Si no quedan permisos, el productor se bloquea hasta que
+/// algún trabajador libere uno (`backpressure`).
+#[derive(Debug)]
+pub struct LimiteChunks {
+    disponibles: Mutex<usize>,
+    liberado: Condvar,
+}
+
+/// Permiso de un chunk en vuelo. Se devuelve al semáforo al ser destruido.
+#[derive(Debug)]
+pub struct PermisoChunk {
+    limite: Arc<LimiteChunks>,
+}
+
+impl LimiteChunks {
+    /// Crea el semáforo con la cantidad máxima de chunks en vuelo (al menos uno).
+    pub fn nuevo(maximo: usize) -> Arc<Self> {
+        Arc::new(LimiteChunks {
+            disponibles: Mutex::new(maximo.max(1)),
+            liberado: Condvar::new(),
+        })
+    }
+
+    /// Toma un permiso, bloqueando al hilo llamador hasta que haya uno disponible.
+    pub fn adquirir(limite: &Arc<Self>) -> PermisoChunk {
+        let mut disponibles = limite
+            .disponibles
+            .lock()
+            .unwrap_or_else(|envenenado| envenenado.into_inner());
+
+        while *disponibles == 0 {
+            disponibles = limite
+                .liberado
+                .wait(disponibles)
+                .unwrap_or_else(|envenenado| envenenado.into_inner());
+        }
+
+        *disponibles -= 1;
+        PermisoChunk {
+            limite: Arc::clone(limite),
+        }
+    }
+}
+
+impl Drop for PermisoChunk {
+    fn drop(&mut self) {
+        let mut disponibles = self
+            .limite
+            .disponibles
+            .lock()
+            .unwrap_or_else(|envenenado| envenenado.into_inner());
+
+        *disponibles += 1;
+        self.limite.liberado.notify_one();
+    }
+}
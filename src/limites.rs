@@ -0,0 +1,152 @@
+//! Este módulo contiene los tamaños configurables de los rankings del ".json" final
+//! y la lectura del archivo de configuración que los define.
+
+// Imports de crates externas.
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+
+// Constantes.
+const VALOR_TODOS: &str = "all";
+const LIMITE_TOP_JUEGOS: usize = 3;
+const LIMITE_IDIOMAS_POR_JUEGO: usize = 3;
+const LIMITE_TOP_IDIOMAS: usize = 3;
+const LIMITE_REVIEWS_POR_IDIOMA: usize = 10;
+const LIMITE_TOP_CALIFICADOS: usize = 3;
+
+// Mensajes.
+const ERROR_LIMITE_INVALIDO: &str = "un número entero positivo o 'all'";
+
+/// Cantidad máxima de elementos de un ranking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "ValorLimite")]
+pub enum Limite {
+    /// Como máximo, esa cantidad de elementos (al menos uno).
+    Cantidad(usize),
+    /// Todos los elementos (`all`).
+    Todos,
+}
+
+/// Valor de un límite tal como se escribe en el archivo de configuración: un número o
+/// un texto (`"all"` o un número entre comillas).
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ValorLimite {
+    Numero(usize),
+    Texto(String),
+}
+
+/// Error de un límite con un valor inválido.
+#[derive(Debug)]
+pub struct ErrorLimite(String);
+
+/// Tamaños de todos los rankings del ".json" final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitesRanking {
+    /// Cantidad de juegos de `top_games`.
+    pub top_juegos: Limite,
+    /// Cantidad de idiomas de cada juego de `top_games`.
+    pub idiomas_por_juego: Limite,
+    /// Cantidad de idiomas de `top_languages`.
+    pub top_idiomas: Limite,
+    /// Cantidad de reseñas de cada idioma de `top_languages`. Es el único límite que se
+    /// aplica durante el procesamiento: con `all`, cada chunk guarda el texto de todas sus
+    /// reseñas, por lo que la memoria crece con el tamaño de la entrada.
+    pub reviews_por_idioma: Limite,
+    /// Cantidad de juegos de `top_rated_games`.
+    pub top_calificados: Limite,
+}
+
+/// Límites indicados en el archivo de configuración o por consola. Los que no se
+/// indican conservan su valor anterior.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LimitesIndicados {
+    pub top_juegos: Option<Limite>,
+    pub idiomas_por_juego: Option<Limite>,
+    pub top_idiomas: Option<Limite>,
+    pub reviews_por_idioma: Option<Limite>,
+    pub top_calificados: Option<Limite>,
+}
+
+impl Limite {
+    /// Interpreta un límite escrito como texto: un número entero positivo o `all`.
+    pub fn desde_texto(valor: &str) -> Result<Self, ErrorLimite> {
+        if valor == VALOR_TODOS {
+            return Ok(Limite::Todos);
+        }
+
+        match valor.parse::<usize>() {
+            Ok(cantidad) if cantidad > 0 => Ok(Limite::Cantidad(cantidad)),
+            _ => Err(ErrorLimite(valor.to_string())),
+        }
+    }
+
+    /// Cantidad máxima de elementos (`usize::MAX` si no hay límite).
+    pub fn capacidad(&self) -> usize {
+        match self {
+            Limite::Cantidad(cantidad) => *cantidad,
+            Limite::Todos => usize::MAX,
+        }
+    }
+}
+
+impl TryFrom<ValorLimite> for Limite {
+    type Error = ErrorLimite;
+
+    fn try_from(valor: ValorLimite) -> Result<Self, Self::Error> {
+        match valor {
+            ValorLimite::Numero(0) => Err(ErrorLimite(0.to_string())),
+            ValorLimite::Numero(cantidad) => Ok(Limite::Cantidad(cantidad)),
+            ValorLimite::Texto(texto) => Limite::desde_texto(&texto),
+        }
+    }
+}
+
+impl fmt::Display for ErrorLimite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' no es {}", self.0, ERROR_LIMITE_INVALIDO)
+    }
+}
+
+impl Error for ErrorLimite {}
+
+impl Default for LimitesRanking {
+    fn default() -> Self {
+        LimitesRanking {
+            top_juegos: Limite::Cantidad(LIMITE_TOP_JUEGOS),
+            idiomas_por_juego: Limite::Cantidad(LIMITE_IDIOMAS_POR_JUEGO),
+            top_idiomas: Limite::Cantidad(LIMITE_TOP_IDIOMAS),
+            reviews_por_idioma: Limite::Cantidad(LIMITE_REVIEWS_POR_IDIOMA),
+            top_calificados: Limite::Cantidad(LIMITE_TOP_CALIFICADOS),
+        }
+    }
+}
+
+impl LimitesIndicados {
+    /// Lee los límites de un archivo de configuración ".json", por ejemplo:
+    ///
+    /// `{ "top_juegos": 25, "top_idiomas": "all", "reviews_por_idioma": 5 }`
+    pub fn desde_archivo(ruta: &str) -> Result<Self, Box<dyn Error>> {
+        let contenido = fs::read_to_string(ruta)?;
+        Ok(serde_json::from_str(&contenido)?)
+    }
+
+    /// Reemplaza en `limites` los límites indicados.
+    pub fn aplicar(&self, limites: &mut LimitesRanking) {
+        let LimitesIndicados {
+            top_juegos,
+            idiomas_por_juego,
+            top_idiomas,
+            reviews_por_idioma,
+            top_calificados,
+        } = *self;
+
+        limites.top_juegos = top_juegos.unwrap_or(limites.top_juegos);
+        limites.idiomas_por_juego = idiomas_por_juego.unwrap_or(limites.idiomas_por_juego);
+        limites.top_idiomas = top_idiomas.unwrap_or(limites.top_idiomas);
+        limites.reviews_por_idioma = reviews_por_idioma.unwrap_or(limites.reviews_por_idioma);
+        limites.top_calificados = top_calificados.unwrap_or(limites.top_calificados);
+    }
+}
@@ -0,0 +1,149 @@
+//! Este módulo contiene la línea de tiempo de las reseñas: cuántas reseñas (y con qué
+//! recomendación) se crearon en cada día, semana o mes, según `timestamp_created`.
+
+// Imports de crates externas.
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+// Imports de funciones/estructuras propias.
+use crate::calificacion::ConteoRecomendaciones;
+use crate::reviews_parseadas::MarcaTemporal;
+
+// Constantes.
+const SEGUNDOS_POR_DIA: i64 = 86_400;
+const DIAS_POR_SEMANA: i64 = 7;
+// El 1970-01-01 fue jueves: sumando 3 días, los lunes quedan en múltiplos de 7.
+const DESFASE_LUNES: i64 = 3;
+// Constantes del algoritmo de Howard Hinnant para convertir días en fechas del
+// calendario gregoriano, por eras de 400 años.
+const DIAS_HASTA_ERA_0: i64 = 719_468;
+const DIAS_POR_ERA: i64 = 146_097;
+
+/// Duración de cada período de la línea de tiempo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Periodo {
+    Dia,
+    /// Semanas de lunes a domingo.
+    Semana,
+    Mes,
+}
+
+/// Reseñas creadas en un período.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ConteoPeriodo {
+    pub cantidad: u64,
+    pub recomendaciones: ConteoRecomendaciones,
+}
+
+/// Cantidad de reseñas de cada período, indexadas por el primer día del período (en días
+/// desde el 1970-01-01, en UTC) y ordenadas cronológicamente.
+///
+/// Sólo se guardan los períodos con alguna reseña, por lo que su tamaño depende del
+/// rango de fechas de las reseñas y no de su cantidad.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct LineaDeTiempo {
+    periodos: BTreeMap<i64, ConteoPeriodo>,
+}
+
+impl Periodo {
+    /// Busca el período por el nombre con el que se indica por consola.
+    pub fn desde_opcion(nombre: &str) -> Option<Self> {
+        match nombre {
+            "dia" => Some(Periodo::Dia),
+            "semana" => Some(Periodo::Semana),
+            "mes" => Some(Periodo::Mes),
+            _ => None,
+        }
+    }
+
+    /// Nombre del período, tal como se muestra en el `.json` de salida.
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Periodo::Dia => "day",
+            Periodo::Semana => "week",
+            Periodo::Mes => "month",
+        }
+    }
+
+    /// Primer día (en días desde el 1970-01-01) del período que contiene a `marca`.
+    pub fn inicio(&self, marca: MarcaTemporal) -> i64 {
+        let dia = marca.div_euclid(SEGUNDOS_POR_DIA);
+        match self {
+            Periodo::Dia => dia,
+            Periodo::Semana => dia - (dia + DESFASE_LUNES).rem_euclid(DIAS_POR_SEMANA),
+            Periodo::Mes => {
+                let (año, mes, _) = fecha_civil(dia);
+                dias_desde_fecha_civil(año, mes, 1)
+            }
+        }
+    }
+
+    /// Etiqueta del período que empieza en `inicio`: `AAAA-MM-DD` para los días y las
+    /// semanas (su lunes) y `AAAA-MM` para los meses.
+    pub fn etiqueta(&self, inicio: i64) -> String {
+        let (año, mes, dia) = fecha_civil(inicio);
+        match self {
+            Periodo::Dia | Periodo::Semana => format!("{año:04}-{mes:02}-{dia:02}"),
+            Periodo::Mes => format!("{año:04}-{mes:02}"),
+        }
+    }
+}
+
+impl LineaDeTiempo {
+    /// Registra una reseña creada en el período que empieza en `inicio`.
+    pub fn registrar(&mut self, inicio: i64, recomendada: Option<bool>) {
+        let conteo = self.periodos.entry(inicio).or_default();
+        conteo.cantidad += 1;
+        conteo.recomendaciones.registrar(recomendada);
+    }
+
+    /// Períodos con alguna reseña, del más antiguo al más reciente, con su primer día.
+    pub fn periodos(&self) -> impl Iterator<Item = (i64, &ConteoPeriodo)> {
+        self.periodos
+            .iter()
+            .map(|(inicio, conteo)| (*inicio, conteo))
+    }
+
+    /// Fusiona los períodos de `self` en `destino`.
+    pub fn merge_into(self, destino: &mut LineaDeTiempo) {
+        for (inicio, conteo) in self.periodos {
+            let destino = destino.periodos.entry(inicio).or_default();
+            destino.cantidad += conteo.cantidad;
+            destino.recomendaciones.sumar(&conteo.recomendaciones);
+        }
+    }
+}
+
+/// Convierte días desde el 1970-01-01 en una fecha (año, mes, día) del calendario
+/// gregoriano.
+fn fecha_civil(dias: i64) -> (i64, i64, i64) {
+    let desde_era_0 = dias + DIAS_HASTA_ERA_0;
+    let era = desde_era_0.div_euclid(DIAS_POR_ERA);
+    let dia_de_era = desde_era_0.rem_euclid(DIAS_POR_ERA);
+    let año_de_era =
+        (dia_de_era - dia_de_era / 1460 + dia_de_era / 36_524 - dia_de_era / 146_096) / 365;
+    let dia_del_año = dia_de_era - (365 * año_de_era + año_de_era / 4 - año_de_era / 100);
+    // Meses contados desde marzo, para que el 29 de febrero quede al final del año.
+    let mes_desde_marzo = (5 * dia_del_año + 2) / 153;
+    let dia = dia_del_año - (153 * mes_desde_marzo + 2) / 5 + 1;
+    let mes = if mes_desde_marzo < 10 {
+        mes_desde_marzo + 3
+    } else {
+        mes_desde_marzo - 9
+    };
+
+    let año = año_de_era + era * 400 + i64::from(mes <= 2);
+    (año, mes, dia)
+}
+
+/// Convierte una fecha (año, mes, día) del calendario gregoriano en días desde el
+/// 1970-01-01. Es la inversa de `fecha_civil`.
+fn dias_desde_fecha_civil(año: i64, mes: i64, dia: i64) -> i64 {
+    let año = if mes <= 2 { año - 1 } else { año };
+    let era = año.div_euclid(400);
+    let año_de_era = año.rem_euclid(400);
+    let mes_desde_marzo = (mes + 9) % 12;
+    let dia_del_año = (153 * mes_desde_marzo + 2) / 5 + dia - 1;
+    let dia_de_era = año_de_era * 365 + año_de_era / 4 - año_de_era / 100 + dia_del_año;
+    era * DIAS_POR_ERA + dia_de_era - DIAS_HASTA_ERA_0
+}
@@ -1,15 +1,69 @@
 //! Este módulo contiene la lógica principal/secuencial del programa.
 
 // Módulos locales utilizados.
+mod acceso_anticipado;
 mod argumentos;
+mod boceto_cuantiles;
+mod calificacion;
+mod canales_compra;
+mod deduplicacion;
+mod entrada;
 mod estadisticas;
 mod estadisticas_serializables;
+mod hyperloglog;
+mod internador;
+mod limite_chunks;
+mod limites;
+mod linea_de_tiempo;
+mod metricas;
 mod procesadores;
+mod reporte_ingesta;
 mod reviews_parseadas;
+mod segmentador;
+mod tiempo_de_juego;
+mod top_k;
 
-// Módulo local para 'test'.
+// Módulos locales para 'test'.
+#[cfg(test)]
+mod tests_acceso_anticipado;
+#[cfg(test)]
+mod tests_calificacion;
+#[cfg(test)]
+mod tests_canales_compra;
 #[cfg(test)]
 mod tests_concurrencia;
+#[cfg(test)]
+mod tests_deduplicacion;
+#[cfg(test)]
+mod tests_desempates;
+#[cfg(test)]
+mod tests_entrada;
+#[cfg(test)]
+mod tests_hyperloglog;
+#[cfg(test)]
+mod tests_ingesta;
+#[cfg(test)]
+mod tests_internador;
+#[cfg(test)]
+mod tests_juegos;
+#[cfg(test)]
+mod tests_limites;
+#[cfg(test)]
+mod tests_linea_de_tiempo;
+#[cfg(test)]
+mod tests_metricas;
+#[cfg(test)]
+mod tests_rendimiento;
+#[cfg(test)]
+mod tests_reviews_parseadas;
+#[cfg(test)]
+mod tests_segmentador;
+#[cfg(test)]
+mod tests_tiempo_de_juego;
+#[cfg(test)]
+mod tests_top_k;
+#[cfg(test)]
+mod tests_utilidades;
 
 // Imports de crates externas.
 use rayon::ThreadPoolBuilder;
@@ -19,12 +73,19 @@ use std::path::Path;
 
 // Imports de funciones/estructuras propias.
 use crate::estadisticas::EstadisticasGlobales;
-use crate::estadisticas_serializables::{ASalidaFinal, SalidaFinal, filtrar_top3};
+use crate::estadisticas_serializables::{
+    ASalidaFinal, OpcionesSalida, SalidaFinal, convertir_linea_de_tiempo,
+    convertir_reporte_ingesta, filtrar_tops,
+};
 use argumentos::parsear_argumentos;
-use procesadores::procesar_csv_con_rayon;
+use procesadores::{OpcionesProcesamiento, procesar_csv_con_rayon};
+use reporte_ingesta::ErrorIngesta;
 
 // Constantes.
 const CODIGO_ERROR: i32 = 1;
+const CODIGO_ERROR_REGISTRO_INVALIDO: i32 = 2;
+const CODIGO_ERROR_ARCHIVO_ILEGIBLE: i32 = 3;
+const CODIGO_ERROR_ENTRADA_ILEGIBLE: i32 = 4;
 const PADRON: u32 = 110675;
 
 // Mensajes.
@@ -37,40 +98,67 @@ const ERROR_CREACION_ARCHIVO: &str = "❌ No se pudo crear el archivo: ";
 const ERROR_ESCRITURA_JSON: &str = "❌ No se pudo escribir el JSON.";
 const MSJ_GUARDADO_RESULTADO: &str = "✅ Estadísticas guardadas en:";
 
+/// Función que elige el código de salida del programa según el error que lo abortó.
+///
+/// Los errores de ingesta del modo estricto tienen un código propio por tipo, para que
+/// quien ejecute el programa pueda distinguirlos sin parsear el mensaje.
+fn codigo_de_salida(error: &(dyn std::error::Error + 'static)) -> i32 {
+    match error.downcast_ref::<ErrorIngesta>() {
+        Some(ErrorIngesta::RegistroInvalido { .. }) => CODIGO_ERROR_REGISTRO_INVALIDO,
+        Some(ErrorIngesta::ArchivoIlegible { .. }) => CODIGO_ERROR_ARCHIVO_ILEGIBLE,
+        Some(ErrorIngesta::EntradaIlegible { .. }) => CODIGO_ERROR_ENTRADA_ILEGIBLE,
+        None => CODIGO_ERROR,
+    }
+}
+
 /// Función que controla:
 /// - La creación del `thread pool`.
-/// - Pasa a procesar el `.csv`.
-/// - Hace el `merge` de los resultados.
+/// - Pasa a procesar los `.csv` de la entrada, cuyos resultados se fusionan en paralelo
+///   dentro de la `pool`.
 ///
-/// Devuelve las estadísticas globales del `.csv` procesado.
-fn procesar_archivo_con_pool(ruta: String, cantidad_threads: usize) -> EstadisticasGlobales {
+/// Devuelve las estadísticas globales de los `.csv` procesados.
+fn procesar_archivo_con_pool(
+    rutas: &[String],
+    cantidad_threads: usize,
+    opciones: &OpcionesProcesamiento,
+) -> EstadisticasGlobales {
     let pool = ThreadPoolBuilder::new()
         .num_threads(cantidad_threads)
         .build()
         .expect(ERROR_CREACION_POOL);
 
-    let mut conteo_global = EstadisticasGlobales::default();
-    pool.install(|| match procesar_csv_con_rayon(ruta) {
-        Ok(parciales) => {
-            for parcial in parciales {
-                parcial.merge_into(&mut conteo_global);
-            }
-        }
-
+    pool.install(|| match procesar_csv_con_rayon(rutas, opciones) {
+        Ok(conteo_global) => conteo_global,
         Err(e) => {
             eprintln!("{} {}.", ERROR_PROC_ARCHIVO, e);
-            std::process::exit(CODIGO_ERROR);
+            std::process::exit(codigo_de_salida(e.as_ref()));
         }
-    });
-
-    conteo_global
+    })
 }
 
 /// Función que filtra los resultados obtenidos del archivo procesado.
 ///
 /// Devuelve la estructura lista para ser escrita en formato `.json`.
-fn preparar_salida_final(conteo: &EstadisticasGlobales) -> SalidaFinal {
-    filtrar_top3(conteo).a_salida_final(PADRON)
+fn preparar_salida_final(
+    conteo: &EstadisticasGlobales,
+    procesamiento: &OpcionesProcesamiento,
+    opciones: &OpcionesSalida,
+) -> SalidaFinal {
+    let estadisticas = filtrar_tops(conteo, procesamiento, opciones);
+    let mut salida = estadisticas.a_salida_final(PADRON, procesamiento, opciones);
+    if procesamiento.deduplicar {
+        salida.duplicates_removed = Some(conteo.ingesta.filas_duplicadas());
+    }
+
+    if let Some(periodo) = procesamiento.linea_de_tiempo {
+        salida.timeline = Some(convertir_linea_de_tiempo(&estadisticas, periodo));
+    }
+
+    if opciones.incluir_reporte_ingesta {
+        salida.ingestion_report = Some(convertir_reporte_ingesta(&conteo.ingesta));
+    }
+
+    salida
 }
 
 /// Función que crea el `.json` con el resultado obtenido.
@@ -99,10 +187,16 @@ fn main() {
     };
 
     let conteo_global = procesar_archivo_con_pool(
-        configuracion.ruta_archivo.clone(),
+        &configuracion.rutas_entrada,
         configuracion.cantidad_threads,
+        &configuracion.procesamiento,
     );
 
-    let salida_final = preparar_salida_final(&conteo_global);
+    conteo_global.ingesta.imprimir();
+    let salida_final = preparar_salida_final(
+        &conteo_global,
+        &configuracion.procesamiento,
+        &configuracion.salida,
+    );
     guardar_json_de_salida(&salida_final, &configuracion.nombre_archivo_salida);
 }
@@ -0,0 +1,169 @@
+//! Este módulo contiene las métricas con las que se pueden ordenar las mejores reseñas
+//! de cada juego e idioma.
+
+// Imports de crates externas.
+use serde::Serialize;
+use std::cmp::Ordering;
+
+// Imports de funciones/estructuras propias.
+use crate::estadisticas::Votos;
+use crate::reviews_parseadas::Reseña;
+
+/// Métrica con la que se ordenan las reseñas de `mejores_reviews` y `top_reviews`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MetricaReseñas {
+    /// Votos útiles (`votes_helpful`), convertidos según la política de votos desbordados.
+    #[default]
+    VotosUtiles,
+    /// Votos graciosos (`votes_funny`).
+    VotosGraciosos,
+    /// Puntaje de utilidad ponderado de Steam (`weighted_vote_score`).
+    PuntajePonderado,
+    /// Cantidad de comentarios (`comment_count`).
+    Comentarios,
+}
+
+/// Rankings de reseñas destacadas que se arman para cada juego (por idioma) y para cada
+/// idioma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankingReseñas {
+    /// Las mejores según la métrica elegida (`mejores_reviews` y `top_reviews`).
+    Mejores,
+    /// Las de más votos graciosos (`votes_funny`).
+    MasGraciosas,
+    /// Las de más comentarios (`comment_count`).
+    MasComentadas,
+}
+
+/// Valor de una reseña según una métrica, tal como se muestra en el `.json` de salida.
+///
+/// Su orden (`Ord`) es total: los decimales se comparan con `f64::total_cmp`. En un mismo
+/// ranking todos los valores son de la misma métrica, pero si se comparan un entero y un
+/// decimal, el entero es el menor.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(untagged)]
+pub enum ValorMetrica {
+    Entero(u64),
+    Decimal(f64),
+}
+
+impl MetricaReseñas {
+    /// Busca la métrica por el nombre con el que se indica por consola.
+    pub fn desde_opcion(nombre: &str) -> Option<Self> {
+        match nombre {
+            "utiles" => Some(MetricaReseñas::VotosUtiles),
+            "graciosos" => Some(MetricaReseñas::VotosGraciosos),
+            "puntaje" => Some(MetricaReseñas::PuntajePonderado),
+            "comentarios" => Some(MetricaReseñas::Comentarios),
+            _ => None,
+        }
+    }
+
+    /// Nombre de la métrica (su columna del dataset), tal como se muestra en el `.json`
+    /// de salida.
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            MetricaReseñas::VotosUtiles => "votes_helpful",
+            MetricaReseñas::VotosGraciosos => "votes_funny",
+            MetricaReseñas::PuntajePonderado => "weighted_vote_score",
+            MetricaReseñas::Comentarios => "comment_count",
+        }
+    }
+
+    /// Valor con el que compite una reseña en los rankings, o `None` si no compite.
+    ///
+    /// Los puntajes ponderados negativos o no finitos no compiten.
+    ///
+    /// # Argumentos
+    /// * `reseña` - Reseña de la que se toma el valor.
+    /// * `votos_convertidos` - Votos útiles de la reseña, ya convertidos según la
+    ///   política de votos desbordados (`None` si se cuenta sin votos).
+    pub fn valor(
+        &self,
+        reseña: &Reseña<'_>,
+        votos_convertidos: Option<Votos>,
+    ) -> Option<ValorMetrica> {
+        match self {
+            MetricaReseñas::VotosUtiles => votos_convertidos.map(ValorMetrica::Entero),
+            MetricaReseñas::VotosGraciosos => reseña.votos_graciosos.map(ValorMetrica::Entero),
+            MetricaReseñas::PuntajePonderado => reseña
+                .puntaje_ponderado
+                .filter(|puntaje| puntaje.is_finite() && *puntaje >= 0.0)
+                // Para `total_cmp`, `-0.0` es menor que `0.0`: se unifican para que ambos
+                // empaten y se desempaten como el resto de las reseñas.
+                .map(|puntaje| ValorMetrica::Decimal(puntaje + 0.0)),
+            MetricaReseñas::Comentarios => reseña.cantidad_comentarios.map(ValorMetrica::Entero),
+        }
+    }
+
+    /// Valor que se muestra cuando no hay ninguna reseña con valor en la métrica.
+    pub fn valor_vacio(&self) -> ValorMetrica {
+        match self {
+            MetricaReseñas::PuntajePonderado => ValorMetrica::Decimal(0.0),
+            _ => ValorMetrica::Entero(0),
+        }
+    }
+}
+
+impl PartialEq for ValorMetrica {
+    fn eq(&self, otro: &Self) -> bool {
+        self.cmp(otro) == Ordering::Equal
+    }
+}
+
+impl Eq for ValorMetrica {}
+
+impl PartialOrd for ValorMetrica {
+    fn partial_cmp(&self, otro: &Self) -> Option<Ordering> {
+        Some(self.cmp(otro))
+    }
+}
+
+impl Ord for ValorMetrica {
+    fn cmp(&self, otro: &Self) -> Ordering {
+        match (self, otro) {
+            (ValorMetrica::Entero(a), ValorMetrica::Entero(b)) => a.cmp(b),
+            (ValorMetrica::Decimal(a), ValorMetrica::Decimal(b)) => a.total_cmp(b),
+            (ValorMetrica::Entero(_), ValorMetrica::Decimal(_)) => Ordering::Less,
+            (ValorMetrica::Decimal(_), ValorMetrica::Entero(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl RankingReseñas {
+    /// Todos los rankings de reseñas destacadas.
+    pub const TODOS: [RankingReseñas; 3] = [
+        RankingReseñas::Mejores,
+        RankingReseñas::MasGraciosas,
+        RankingReseñas::MasComentadas,
+    ];
+
+    /// Valor con el que compite una reseña en el ranking, o `None` si no compite.
+    ///
+    /// En los rankings de las más graciosas y las más comentadas no compiten las reseñas
+    /// sin votos graciosos o sin comentarios.
+    ///
+    /// # Argumentos
+    /// * `metrica` - Métrica con la que se ordenan las mejores reseñas.
+    /// * `reseña` - Reseña de la que se toma el valor.
+    /// * `votos_convertidos` - Votos útiles de la reseña, ya convertidos según la
+    ///   política de votos desbordados (`None` si se cuenta sin votos).
+    pub fn valor(
+        &self,
+        metrica: MetricaReseñas,
+        reseña: &Reseña<'_>,
+        votos_convertidos: Option<Votos>,
+    ) -> Option<ValorMetrica> {
+        match self {
+            RankingReseñas::Mejores => metrica.valor(reseña, votos_convertidos),
+            RankingReseñas::MasGraciosas => reseña
+                .votos_graciosos
+                .filter(|votos| *votos > 0)
+                .map(ValorMetrica::Entero),
+            RankingReseñas::MasComentadas => reseña
+                .cantidad_comentarios
+                .filter(|comentarios| *comentarios > 0)
+                .map(ValorMetrica::Entero),
+        }
+    }
+}
@@ -1,167 +1,1017 @@
 //! Este módulo contiene la lógica de los hilos del programa que procesan la informacion.
 
 // Imports de crates externas.
-use csv::ReaderBuilder;
+use csv::{ByteRecord, Position, ReaderBuilder};
 use rayon::prelude::*;
+use std::cell::RefCell;
+use std::convert::Infallible;
 use std::error::Error;
-use std::fs::{File, read_dir};
-use std::path::Path;
+use std::io::{Cursor, SeekFrom};
+use std::num::{IntErrorKind, ParseIntError};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
 
 // Imports de funciones/estructuras propias.
-use crate::estadisticas::EstadisticasParciales;
+use crate::canales_compra::CanalCompra;
+use crate::deduplicacion::IndiceReseñas;
+use crate::entrada::{OpcionesEntrada, RUTA_ENTRADA_ESTANDAR, abrir_archivo, buscar_archivos};
+use crate::estadisticas::{
+    ClaveJuego, EstadisticasGlobales, EstadisticasParciales, InfoIdioma, ReseñaDestacada, Votos,
+};
+use crate::hyperloglog::PRECISION_POR_DEFECTO;
+use crate::internador::{CacheInternado, Internador};
+use crate::limite_chunks::{LimiteChunks, PermisoChunk};
+use crate::limites::LimitesRanking;
+use crate::linea_de_tiempo::Periodo;
+use crate::metricas::{MetricaReseñas, RankingReseñas};
+use crate::reporte_ingesta::{ErrorIngesta, MotivoRechazo, Rechazo, ReporteArchivo};
 use crate::reviews_parseadas::Reseña;
+use crate::segmentador::{BloqueCsv, Segmentador, TamañoChunk};
 
 // Constantes.
 const CHUNK_SIZE: usize = 100_000;
-const EXTENSION_ARCHIVO_A_PROCESAR: &str = "csv";
+const CHUNKS_EN_VUELO_POR_HILO: usize = 2;
+const COLUMNA_VOTOS_UTILES: &str = "votes_helpful";
 
 // Mensajes.
-const ERROR_PATH_INVALIDO: &str = "❌ El path no es un directorio válido.";
 const ERROR_ABRIR_ARCHIVO: &str = "⚠️ Error al abrir el archivo";
-const ERROR_ENTRADA_DIRECTORIO: &str = "⚠️ Error al leer entrada del directorio:";
+const ERROR_LEER_ARCHIVO: &str = "⚠️ Error al leer el archivo";
+const ERROR_HILO_PRODUCTOR: &str = "❌ Un hilo productor terminó con pánico.";
+const ERROR_REINICIAR_LECTOR: &str = "❌ No se pudo reiniciar el lector de chunks.";
+const ERROR_DEDUPLICAR_ENTRADA_ESTANDAR: &str =
+    "❌ No se puede deduplicar leyendo de la entrada estándar, ya que se lee dos veces.";
 
-/// Valida que el path recibido sea un directorio válido.
+thread_local! {
+    /// Lector de `.csv` de cada hilo trabajador, que se reutiliza en todos los chunks que
+    /// procesa en lugar de armar uno por chunk. Cada chunk se carga en su fuente y se
+    /// reinicia el lector; los encabezados se fijan vacíos para que nunca lea el primer
+    /// registro como encabezado. Mientras se recorre un chunk no se usa Rayon, por lo que
+    /// un hilo nunca toma otro chunk con el lector prestado.
+    static LECTOR_CHUNKS: RefCell<csv::Reader<Cursor<Vec<u8>>>> = RefCell::new({
+        let mut lector = ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(Cursor::default());
+
+        lector.set_byte_headers(ByteRecord::new());
+        lector
+    });
+}
+
+/// Reseña deserializada de una fila, con sus votos útiles ya convertidos según la política
+/// de votos desbordados y el rechazo correspondiente al primer valor inválido de sus
+/// columnas opcionales (si tiene alguno).
+type ReseñaValidada<'a> = (Reseña<'a>, Option<Votos>, Option<Rechazo>);
+
+/// Posición de un chunk dentro de la entrada: (índice del archivo, número de chunk en el archivo).
+///
+/// Se usa para ubicar las reseñas de un chunk dentro de la entrada, por ejemplo para elegir
+/// qué copia de una reseña se conserva al deduplicar.
+type OrdenChunk = (usize, usize);
+
+/// Qué hacer con las reseñas cuyos `votes_helpful` no entran en un `u32`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PoliticaVotosDesbordados {
+    /// Se descarta la reseña completa (y se registra en el reporte de ingesta).
+    #[default]
+    Descartar,
+    /// Se cuenta la reseña, pero no compite por las mejores reseñas.
+    IgnorarVotos,
+    /// Se cuenta la reseña con `u32::MAX` votos.
+    Saturar,
+    /// Se cuenta la reseña con sus votos reales, siempre que entren en un `u64`.
+    Ampliar,
+}
+
+impl PoliticaVotosDesbordados {
+    /// Busca la política por el nombre con el que se indica por consola.
+    pub fn desde_opcion(nombre: &str) -> Option<Self> {
+        match nombre {
+            "descartar" => Some(PoliticaVotosDesbordados::Descartar),
+            "ignorar-votos" => Some(PoliticaVotosDesbordados::IgnorarVotos),
+            "saturar" => Some(PoliticaVotosDesbordados::Saturar),
+            "ampliar" => Some(PoliticaVotosDesbordados::Ampliar),
+            _ => None,
+        }
+    }
+
+    /// Nombre de la política, tal como se muestra en el `.json` de salida.
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            PoliticaVotosDesbordados::Descartar => "drop",
+            PoliticaVotosDesbordados::IgnorarVotos => "ignore_votes",
+            PoliticaVotosDesbordados::Saturar => "saturate",
+            PoliticaVotosDesbordados::Ampliar => "widen",
+        }
+    }
+
+    /// Convierte `votes_helpful` a número aplicando la política.
+    ///
+    /// # Retorna
+    /// * `Ok(Some(votos))` con los votos a contar, `Ok(None)` si la reseña se cuenta sin
+    ///   votos, o el error de la conversión si la reseña se descarta.
+    fn convertir_votos(&self, texto: &str) -> Result<Option<Votos>, ParseIntError> {
+        match texto.parse::<u32>() {
+            Ok(votos) => Ok(Some(Votos::from(votos))),
+            Err(e) if *e.kind() == IntErrorKind::PosOverflow => match self {
+                PoliticaVotosDesbordados::Descartar => Err(e),
+                PoliticaVotosDesbordados::IgnorarVotos => Ok(None),
+                PoliticaVotosDesbordados::Saturar => Ok(Some(Votos::from(u32::MAX))),
+                PoliticaVotosDesbordados::Ampliar => texto.parse::<Votos>().map(Some),
+            },
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Opciones que controlan el 'pipeline' de lectura y procesamiento de los `.csv`.
+#[derive(Debug, Clone)]
+pub struct OpcionesProcesamiento {
+    /// Tamaño de cada chunk, en registros o en bytes.
+    pub tamaño_chunk: TamañoChunk,
+    /// Cantidad máxima de chunks leídos y todavía no procesados. Si es `None`, se usan
+    /// `CHUNKS_EN_VUELO_POR_HILO` chunks por cada hilo de la pool.
+    pub max_chunks_en_vuelo: Option<usize>,
+    /// Si es `true`, la primera fila malformada o archivo ilegible aborta el procesamiento
+    /// en lugar de registrarse en el reporte de ingesta.
+    pub estricto: bool,
+    /// Opciones de búsqueda de los archivos a procesar.
+    pub entrada: OpcionesEntrada,
+    /// Qué hacer con las reseñas cuyos votos no entran en un `u32`.
+    pub votos_desbordados: PoliticaVotosDesbordados,
+    /// Si es `true`, de las reseñas con el mismo `review_id` sólo se cuenta la
+    /// actualizada más recientemente.
+    pub deduplicar: bool,
+    /// Tamaños de los rankings del ".json" final. Durante el procesamiento sólo se usa
+    /// `reviews_por_idioma`, que acota las reseñas que se guardan de cada idioma.
+    pub limites: LimitesRanking,
+    /// Métrica con la que se ordenan las mejores reseñas de cada juego e idioma.
+    pub metrica_reviews: MetricaReseñas,
+    /// Duración de los períodos de la línea de tiempo de cada juego e idioma. Si es
+    /// `None`, no se arma la línea de tiempo.
+    pub linea_de_tiempo: Option<Periodo>,
+    /// Precisión de los estimadores de autores distintos: cada estimador usa
+    /// `2^precision_autores` registros de un byte.
+    pub precision_autores: u8,
+}
+
+impl Default for OpcionesProcesamiento {
+    fn default() -> Self {
+        OpcionesProcesamiento {
+            tamaño_chunk: TamañoChunk::Filas(CHUNK_SIZE),
+            max_chunks_en_vuelo: None,
+            estricto: false,
+            entrada: OpcionesEntrada::default(),
+            votos_desbordados: PoliticaVotosDesbordados::default(),
+            deduplicar: false,
+            limites: LimitesRanking::default(),
+            metrica_reviews: MetricaReseñas::default(),
+            linea_de_tiempo: None,
+            precision_autores: PRECISION_POR_DEFECTO,
+        }
+    }
+}
+
+/// Chunk de reseñas todavía sin parsear, listo para ser procesado por un hilo trabajador.
+///
+/// Mientras el chunk exista retiene su permiso, que se libera al terminar de procesarlo.
+struct Chunk {
+    orden: OrdenChunk,
+    ruta: Arc<String>,
+    encabezados: Arc<ByteRecord>,
+    bloque: BloqueCsv,
+    _permiso: PermisoChunk,
+}
+
+/// Error que aborta el procesamiento en el modo estricto, con la posición del chunk (o
+/// de la lectura) en la que ocurrió.
+type ErrorChunk = (OrdenChunk, ErrorIngesta);
+
+/// Lo que los productores mandan por el canal: un chunk o el error que aborta el
+/// procesamiento en el modo estricto.
+type ResultadoChunk = Result<Chunk, ErrorChunk>;
+
+/// Error más temprano de la entrada entre los que encontraron los hilos en el modo
+/// estricto.
+///
+/// Como los chunks se procesan en paralelo y en cualquier orden, el primer error que se
+/// encuentra no es necesariamente el primero de la entrada. Se guarda el del chunk de
+/// menor `OrdenChunk`, por lo que el error informado es siempre el mismo; los chunks
+/// posteriores a él ya no pueden cambiarlo, y se descartan sin procesarlos.
+#[derive(Default)]
+struct PrimerError {
+    error: Mutex<Option<ErrorChunk>>,
+}
+
+impl PrimerError {
+    /// Indica si ya se encontró un error anterior al chunk `orden`.
+    fn hay_error_antes_de(&self, orden: OrdenChunk) -> bool {
+        self.error
+            .lock()
+            .unwrap_or_else(|envenenado| envenenado.into_inner())
+            .as_ref()
+            .is_some_and(|(orden_error, _)| *orden_error < orden)
+    }
+
+    /// Registra un error, si es anterior al guardado.
+    fn registrar(&self, error: ErrorChunk) {
+        let mut guardado = self
+            .error
+            .lock()
+            .unwrap_or_else(|envenenado| envenenado.into_inner());
+
+        if guardado
+            .as_ref()
+            .is_none_or(|(orden_guardado, _)| error.0 < *orden_guardado)
+        {
+            *guardado = Some(error);
+        }
+    }
+
+    /// Devuelve el error más temprano, si hubo alguno.
+    fn en_error(self) -> Option<ErrorIngesta> {
+        self.error
+            .into_inner()
+            .unwrap_or_else(|envenenado| envenenado.into_inner())
+            .map(|(_, error)| error)
+    }
+}
+
+/// Informa un error al abrir o leer un archivo.
+///
+/// En el modo estricto el error se manda por el canal para abortar el procesamiento;
+/// si no, sólo se imprime la advertencia y se sigue con el resto de los archivos.
 ///
 /// # Argumentos
-/// * `path` - Ruta del directorio a validar.
+/// * `orden` - Posición del chunk que no se pudo leer.
 ///
 /// # Retorna
-/// * `Ok(())` si es válido, o un error si no lo es.
-fn validar_directorio(path: &Path) -> Result<(), Box<dyn Error>> {
-    if !path.exists() || !path.is_dir() {
-        return Err(ERROR_PATH_INVALIDO.into());
+/// * `true` si los productores pueden seguir leyendo archivos.
+fn informar_error_lectura(
+    mensaje: &str,
+    ruta: &Path,
+    orden: OrdenChunk,
+    error: std::io::Error,
+    estricto: bool,
+    tx: &Sender<ResultadoChunk>,
+) -> bool {
+    if !estricto {
+        eprintln!("{} {}: {}", mensaje, ruta.display(), error);
+        return true;
     }
 
-    Ok(())
+    let _ = tx.send(Err((
+        orden,
+        ErrorIngesta::ArchivoIlegible {
+            archivo: ruta.display().to_string(),
+            detalle: error.to_string(),
+        },
+    )));
+
+    false
 }
 
-/// Procesa un archivo `.csv` leyendo sus reseñas y agrupándolas en chunks.
+/// Divide un archivo `.csv` en chunks de registros completos y los manda al canal.
+///
+/// Los archivos comprimidos se descomprimen al vuelo mientras se leen. El parseo de las
+/// reseñas no se hace acá, sino en los hilos trabajadores.
 ///
 /// # Argumentos
+/// * `indice` - Posición del archivo dentro de la entrada.
 /// * `ruta` - Ruta del archivo a procesar.
+/// * `opciones` - Opciones de tamaño de chunk y de modo estricto.
+/// * `limite` - Semáforo de chunks en vuelo; bloquea la lectura cuando se alcanza el máximo.
 /// * `tx` - Canal para enviar los chunks hacia los hilos trabajadores.
-fn procesar_archivo_csv(ruta: &Path, tx: Sender<Vec<Reseña>>) {
-    let archivo = match File::open(ruta) {
+///
+/// # Retorna
+/// * `true` si los productores pueden seguir leyendo archivos, o `false` si el
+///   procesamiento se abortó.
+fn procesar_archivo_csv(
+    indice: usize,
+    ruta: &Path,
+    opciones: &OpcionesProcesamiento,
+    limite: &Arc<LimiteChunks>,
+    tx: &Sender<ResultadoChunk>,
+) -> bool {
+    let archivo = match abrir_archivo(ruta) {
         Ok(f) => f,
         Err(e) => {
-            eprintln!("{} {}: {}", ERROR_ABRIR_ARCHIVO, ruta.display(), e);
-            return;
+            let orden = (indice, 0);
+            return informar_error_lectura(
+                ERROR_ABRIR_ARCHIVO,
+                ruta,
+                orden,
+                e,
+                opciones.estricto,
+                tx,
+            );
         }
     };
 
-    let mut lector = ReaderBuilder::new()
-        .has_headers(true)
-        .flexible(true)
-        .from_reader(archivo);
+    let mut segmentador = match Segmentador::nuevo(archivo, opciones.tamaño_chunk) {
+        Ok(Some(s)) => s,
+        Ok(None) => return true,
+        Err(e) => {
+            let orden = (indice, 0);
+            return informar_error_lectura(
+                ERROR_LEER_ARCHIVO,
+                ruta,
+                orden,
+                e,
+                opciones.estricto,
+                tx,
+            );
+        }
+    };
+
+    let ruta_chunks = Arc::new(ruta.display().to_string());
+    let encabezados = Arc::new(segmentador.encabezados().clone());
+    for numero in 0.. {
+        let permiso = LimiteChunks::adquirir(limite);
+        let bloque = match segmentador.next() {
+            Some(Ok(b)) => b,
+            None => return true,
+            Some(Err(e)) => {
+                let orden = (indice, numero);
+                return informar_error_lectura(
+                    ERROR_LEER_ARCHIVO,
+                    ruta,
+                    orden,
+                    e,
+                    opciones.estricto,
+                    tx,
+                );
+            }
+        };
+
+        let chunk = Chunk {
+            orden: (indice, numero),
+            ruta: Arc::clone(&ruta_chunks),
+            encabezados: Arc::clone(&encabezados),
+            bloque,
+            _permiso: permiso,
+        };
 
-    let mut buffer_chunk = Vec::with_capacity(CHUNK_SIZE);
-    for reseña in lector.deserialize::<Reseña>().flatten() {
-        buffer_chunk.push(reseña);
-        if buffer_chunk.len() == CHUNK_SIZE {
-            tx.send(std::mem::take(&mut buffer_chunk)).unwrap();
+        if tx.send(Ok(chunk)).is_err() {
+            return false;
         }
     }
 
-    if !buffer_chunk.is_empty() {
-        tx.send(buffer_chunk).unwrap();
-    }
+    true
 }
 
-/// Lanza un hilo 'Productor' que recorre el directorio buscando archivos `.csv`,
-/// y manda los chunks al canal correspondiente.
+/// Lanza los hilos 'Productores', que se reparten los archivos a procesar y
+/// mandan sus chunks al canal correspondiente.
+///
+/// Se lanza a lo sumo un productor por hilo de la pool, para que varios archivos
+/// se lean al mismo tiempo.
+///
+/// Todos los productores comparten el mismo límite de chunks en vuelo, por lo que la
+/// memoria ocupada por chunks pendientes no depende de la cantidad de archivos.
 ///
 /// # Argumentos
-/// * `dir_path` - Ruta del directorio a escanear.
+/// * `archivos` - Rutas de los archivos a procesar.
+/// * `opciones` - Opciones de tamaño de chunk, de chunks en vuelo y de modo estricto.
 /// * `tx` - Canal para enviar los chunks.
 ///
 /// # Retorna
-/// * `JoinHandle<()>` del hilo productor.
-fn spawn_productor_directorio(
-    dir_path: &Path,
-    tx: Sender<Vec<Reseña>>,
-) -> std::thread::JoinHandle<()> {
-    let dir = dir_path.to_path_buf();
-    std::thread::spawn(move || {
-        for entry in read_dir(&dir).unwrap() {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(e) => {
-                    eprintln!("{} {}", ERROR_ENTRADA_DIRECTORIO, e);
-                    continue;
+/// * `Vec<JoinHandle<()>>` con los hilos productores.
+fn spawn_productores(
+    archivos: Vec<PathBuf>,
+    opciones: &OpcionesProcesamiento,
+    tx: Sender<ResultadoChunk>,
+) -> Vec<JoinHandle<()>> {
+    let hilos = rayon::current_num_threads();
+    let cantidad_productores = hilos.min(archivos.len());
+    let limite = LimiteChunks::nuevo(
+        opciones
+            .max_chunks_en_vuelo
+            .unwrap_or(hilos * CHUNKS_EN_VUELO_POR_HILO),
+    );
+
+    let opciones = Arc::new(opciones.clone());
+    let archivos = Arc::new(archivos);
+    let siguiente = Arc::new(AtomicUsize::new(0));
+    (0..cantidad_productores)
+        .map(|_| {
+            let archivos = Arc::clone(&archivos);
+            let opciones = Arc::clone(&opciones);
+            let siguiente = Arc::clone(&siguiente);
+            let limite = Arc::clone(&limite);
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                loop {
+                    let indice = siguiente.fetch_add(1, Ordering::Relaxed);
+                    let Some(ruta) = archivos.get(indice) else {
+                        break;
+                    };
+
+                    if !procesar_archivo_csv(indice, ruta, &opciones, &limite, &tx) {
+                        break;
+                    }
                 }
+            })
+        })
+        .collect()
+}
+
+/// Recorre todos los chunks de los archivos, procesándolos en paralelo.
+///
+/// Lanza los hilos productores y luego usa la pool de Rayon para procesar los chunks
+/// a medida que llegan. Cada hilo acumula los resultados de sus chunks con `combinar`
+/// y, al final, los acumulados se combinan de a pares en forma de árbol, también en
+/// paralelo. Como los resultados se consumen al combinarlos, nunca se guardan todos a
+/// la vez.
+///
+/// `combinar` debe ser asociativa y conmutativa, ya que los chunks se combinan en el
+/// orden en que se terminan de procesar.
+///
+/// En el modo estricto, el error informado es el del chunk más temprano de la entrada
+/// (ver `PrimerError`), sin importar en qué orden se procesen los chunks.
+///
+/// # Argumentos
+/// * `archivos` - Archivos a recorrer.
+/// * `opciones` - Opciones del 'pipeline' de procesamiento.
+/// * `procesar` - Función que procesa cada chunk.
+/// * `combinar` - Función que combina los resultados de dos grupos de chunks.
+///
+/// # Retorna
+/// * `Ok(T)` con la combinación de los resultados de todos los chunks, o un error si
+///   falló algo.
+fn recorrer_chunks<T, F>(
+    archivos: &[PathBuf],
+    opciones: &OpcionesProcesamiento,
+    procesar: F,
+    combinar: fn(T, T) -> T,
+) -> Result<T, Box<dyn Error>>
+where
+    T: Default + Send,
+    F: Fn(Chunk) -> Result<T, ErrorIngesta> + Sync + Send,
+{
+    let (tx, rx) = mpsc::channel::<ResultadoChunk>();
+    let productores = spawn_productores(archivos.to_vec(), opciones, tx);
+    let primer_error = PrimerError::default();
+    let resultado = rx
+        .into_iter()
+        .par_bridge()
+        .fold(T::default, |acumulado, chunk| {
+            let orden = match &chunk {
+                Ok(chunk) => chunk.orden,
+                Err((orden, _)) => *orden,
             };
+            if primer_error.hay_error_antes_de(orden) {
+                return acumulado;
+            }
+
+            match chunk.and_then(|chunk| procesar(chunk).map_err(|error| (orden, error))) {
+                Ok(parcial) => combinar(acumulado, parcial),
+                Err(error) => {
+                    primer_error.registrar(error);
+                    acumulado
+                }
+            }
+        })
+        .reduce(T::default, combinar);
+
+    for productor in productores {
+        productor.join().map_err(|_| ERROR_HILO_PRODUCTOR)?;
+    }
+
+    match primer_error.en_error() {
+        Some(error) => Err(error.into()),
+        None => Ok(resultado),
+    }
+}
 
-            let ruta = entry.path();
-            if ruta.is_file()
-                && ruta
-                    .extension()
-                    .is_some_and(|ext| ext == EXTENSION_ARCHIVO_A_PROCESAR)
+/// Función principal que coordina el procesamiento de todos los `.csv` de la entrada.
+///
+/// Si se pidió deduplicar, primero se hace una pasada que indexa qué copia de cada
+/// reseña se conserva, y luego se procesan los archivos descartando el resto.
+///
+/// # Argumentos
+/// * `rutas` - Rutas de los archivos y directorios a procesar.
+/// * `opciones` - Opciones del 'pipeline' de procesamiento.
+///
+/// # Retorna
+/// * `Ok(EstadisticasGlobales)` con las estadísticas de todos los archivos, o un error si
+///   falló algo.
+pub fn procesar_csv_con_rayon(
+    rutas: &[String],
+    opciones: &OpcionesProcesamiento,
+) -> Result<EstadisticasGlobales, Box<dyn Error>> {
+    procesar_csv_registrando(rutas, opciones, registrar_reseña)
+}
+
+/// Igual que `procesar_csv_con_rayon`, pero registra cada reseña aceptada con `registrar`
+/// en lugar de con `registrar_reseña`.
+///
+/// Permite medir variantes del registro de las reseñas (por ejemplo, copiando sus campos
+/// de texto) con el mismo 'pipeline' de procesamiento.
+pub fn procesar_csv_registrando<R>(
+    rutas: &[String],
+    opciones: &OpcionesProcesamiento,
+    registrar: R,
+) -> Result<EstadisticasGlobales, Box<dyn Error>>
+where
+    R: Fn(
+            &mut EstadisticasParciales,
+            &mut CacheInternado<'_>,
+            &Reseña<'_>,
+            Option<Votos>,
+            &OpcionesProcesamiento,
+        ) + Sync,
+{
+    let archivos = buscar_archivos(rutas, &opciones.entrada, opciones.estricto)?;
+    let ganadoras = if opciones.deduplicar {
+        Some(indexar_reseñas(&archivos, opciones)?)
+    } else {
+        None
+    };
+
+    let internador = Internador::default();
+    recorrer_chunks(
+        &archivos,
+        opciones,
+        |chunk| procesar_chunk(chunk, opciones, ganadoras.as_ref(), &internador, &registrar),
+        EstadisticasGlobales::combinar,
+    )
+}
+
+/// Primera pasada del modo deduplicado: indexa, por cada `review_id`, la copia de la
+/// reseña que se conserva.
+///
+/// # Argumentos
+/// * `archivos` - Archivos a indexar.
+/// * `opciones` - Opciones del 'pipeline' de procesamiento.
+///
+/// # Retorna
+/// * `Ok(IndiceReseñas)` con las copias a conservar, o un error si falló algo.
+fn indexar_reseñas(
+    archivos: &[PathBuf],
+    opciones: &OpcionesProcesamiento,
+) -> Result<IndiceReseñas, Box<dyn Error>> {
+    if archivos
+        .iter()
+        .any(|archivo| archivo == Path::new(RUTA_ENTRADA_ESTANDAR))
+    {
+        return Err(ERROR_DEDUPLICAR_ENTRADA_ESTANDAR.into());
+    }
+
+    recorrer_chunks(
+        archivos,
+        opciones,
+        |chunk| Ok(indexar_chunk(chunk, opciones)),
+        IndiceReseñas::combinar,
+    )
+}
+
+/// Indexa las reseñas válidas de un chunk.
+///
+/// Las filas que se rechazan no compiten por ser la copia a conservar; se informan
+/// recién en la segunda pasada.
+fn indexar_chunk(chunk: Chunk, opciones: &OpcionesProcesamiento) -> IndiceReseñas {
+    let mut indice = IndiceReseñas::default();
+    let recorrido: Result<(), Infallible> = validar_reseñas(
+        chunk.bloque,
+        &chunk.encabezados,
+        opciones,
+        |linea, resultado| {
+            if let Ok((reseña, _, _)) = resultado
+                && let Some(id) = reseña.id_reseña
             {
-                procesar_archivo_csv(&ruta, tx.clone());
+                indice.registrar(id, reseña.actualizada, (chunk.orden.0, linea));
             }
-        }
+
+            Ok(())
+        },
+    );
+
+    let Ok(()) = recorrido;
+    indice
+}
+
+/// Parsea las reseñas de un chunk.
+///
+/// Cada registro se lee siempre en el mismo `ByteRecord`, y la reseña se deserializa
+/// tomando prestados sus campos de texto, por lo que sólo vive mientras se la visita.
+/// Los registros se leen con el lector del hilo (ver `LECTOR_CHUNKS`).
+///
+/// # Argumentos
+/// * `bloque` - Registros crudos del chunk, con la línea en la que empieza cada uno.
+/// * `encabezados` - Encabezados del archivo del chunk.
+/// * `visitar` - Función que recibe, por cada registro, la línea del archivo en la que
+///   empieza junto a la reseña deserializada y el primer valor inválido de sus columnas
+///   opcionales (si tiene alguno), o el motivo por el que no se pudo deserializar. Si
+///   devuelve un error, se deja de recorrer el chunk.
+///
+/// # Retorna
+/// * `Ok(())` si se recorrió todo el chunk, o el primer error de `visitar`.
+fn parsear_reseñas<E>(
+    bloque: BloqueCsv,
+    encabezados: &ByteRecord,
+    mut visitar: impl FnMut(u64, Result<(Reseña<'_>, Option<Rechazo>), Rechazo>) -> Result<(), E>,
+) -> Result<(), E> {
+    LECTOR_CHUNKS.with_borrow_mut(|lector| {
+        *lector.get_mut() = Cursor::new(bloque.datos);
+        lector
+            .seek_raw(SeekFrom::Start(0), Position::new())
+            .expect(ERROR_REINICIAR_LECTOR);
+
+        let recorrido = recorrer_registros(lector, bloque.lineas, encabezados, &mut visitar);
+        // Se libera el chunk sin esperar al siguiente.
+        *lector.get_mut() = Cursor::default();
+        recorrido
     })
 }
 
-/// Función principal que coordina el procesamiento de todos los `.csv` de un directorio.
+/// Recorre los registros de un chunk ya cargado en `lector`, deserializando sus reseñas.
+///
+/// # Argumentos
+/// * `lineas` - Línea del archivo en la que empieza cada registro, según el segmentador.
+fn recorrer_registros<E>(
+    lector: &mut csv::Reader<Cursor<Vec<u8>>>,
+    lineas: Vec<u64>,
+    encabezados: &ByteRecord,
+    visitar: &mut impl FnMut(u64, Result<(Reseña<'_>, Option<Rechazo>), Rechazo>) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut registro = ByteRecord::new();
+    let mut corregido = ByteRecord::new();
+    let mut lineas = lineas.into_iter();
+    let mut linea = 0;
+    loop {
+        let resultado = match lector.read_byte_record(&mut registro) {
+            Ok(false) => return Ok(()),
+            Ok(true) => match deserializar_reseña(&registro, &mut corregido, encabezados) {
+                // Los campos opcionales pueden faltar en el encabezado, pero no en un
+                // registro que tiene menos campos que su encabezado.
+                Ok(_) if registro.len() < encabezados.len() => {
+                    Err(Rechazo::campos_faltantes(encabezados.len(), registro.len()))
+                }
+                Ok(reseña) => Ok(reseña),
+                Err(e) => {
+                    // Si faltan columnas al final del registro, se informa la primera faltante.
+                    let mut rechazo = Rechazo::desde_error_csv(&e);
+                    if rechazo.motivo == MotivoRechazo::ColumnaFaltante && rechazo.columna.is_none()
+                    {
+                        rechazo.columna = Some(registro.len() as u64 + 1);
+                    }
+
+                    Err(rechazo)
+                }
+            },
+            Err(e) => Err(Rechazo::desde_error_csv(&e)),
+        };
+
+        // El segmentador encuentra los mismos inicios de registro que `csv`, por lo que
+        // cada registro leído tiene su línea.
+        linea = lineas.next().unwrap_or(linea);
+        visitar(linea, resultado)?;
+    }
+}
+
+/// Deserializa la reseña de un registro.
+///
+/// Si alguna columna opcional tiene un valor que no corresponde a su tipo, se deserializa
+/// una copia del registro (`corregido`) en la que ese campo está vacío, hasta que no
+/// queden valores inválidos. Así, el registro sólo se copia si tiene algún valor inválido.
 ///
-/// Lanza el hilo productor y luego usa la pool de Rayon para procesar los chunks de reseñas en paralelo.
+/// # Retorna
+/// * `Ok` con la reseña y el rechazo correspondiente al primer valor inválido de sus
+///   columnas opcionales (si tiene alguno), o el error de deserialización si no se puede
+///   deserializar aun sin esos valores.
+fn deserializar_reseña<'r>(
+    registro: &'r ByteRecord,
+    corregido: &'r mut ByteRecord,
+    encabezados: &'r ByteRecord,
+) -> Result<(Reseña<'r>, Option<Rechazo>), csv::Error> {
+    let mut error = match registro.deserialize(Some(encabezados)) {
+        Ok(reseña) => return Ok((reseña, None)),
+        Err(e) => e,
+    };
+
+    corregido.clone_from(registro);
+    let mut primer_invalido: Option<Rechazo> = None;
+    while MotivoRechazo::desde_error_csv(&error) == MotivoRechazo::CampoInvalido {
+        let indice = indice_campo_invalido(corregido, encabezados, &error);
+        let rechazo = primer_invalido.get_or_insert_with(|| Rechazo::desde_error_csv(&error));
+        rechazo.columna = rechazo.columna.or(Some(indice as u64 + 1));
+
+        *corregido = con_campos_vacios(corregido, |i| i == indice);
+        match corregido.deserialize::<Reseña>(Some(encabezados)) {
+            Ok(_) => {
+                let corregido: &'r ByteRecord = corregido;
+                let reseña = corregido.deserialize(Some(encabezados))?;
+                return Ok((reseña, primer_invalido));
+            }
+            Err(e) => error = e,
+        }
+    }
+
+    Err(error)
+}
+
+/// Busca el índice del campo con el valor inválido que causó un error de deserialización.
+///
+/// Los errores de los tipos con un formato propio (booleanos y marcas temporales) no
+/// indican su campo. Como un campo vacío siempre se puede deserializar, en ese caso el
+/// campo inválido es el último del prefijo más corto del registro que no se puede
+/// deserializar (vaciando el resto de sus campos), que se busca por bisección.
+fn indice_campo_invalido(
+    registro: &ByteRecord,
+    encabezados: &ByteRecord,
+    error: &csv::Error,
+) -> usize {
+    if let csv::ErrorKind::Deserialize { err, .. } = error.kind()
+        && let Some(indice) = err.field()
+    {
+        return indice as usize;
+    }
+
+    let se_deserializa = |largo: usize| {
+        con_campos_vacios(registro, |i| i >= largo)
+            .deserialize::<Reseña>(Some(encabezados))
+            .is_ok()
+    };
+
+    let (mut valido, mut invalido) = (0, registro.len());
+    while invalido - valido > 1 {
+        let medio = (valido + invalido) / 2;
+        if se_deserializa(medio) {
+            valido = medio;
+        } else {
+            invalido = medio;
+        }
+    }
+
+    invalido - 1
+}
+
+/// Copia un registro vaciando los campos cuyo índice cumple `vaciar`.
+fn con_campos_vacios(registro: &ByteRecord, vaciar: impl Fn(usize) -> bool) -> ByteRecord {
+    let mut copia: ByteRecord = registro
+        .iter()
+        .enumerate()
+        .map(|(i, campo)| if vaciar(i) { &[][..] } else { campo })
+        .collect();
+    copia.set_position(registro.position().cloned());
+    copia
+}
+
+/// Parsea las reseñas de un chunk y convierte sus votos según la política de votos
+/// desbordados.
 ///
 /// # Argumentos
-/// * `directorio` - Ruta del directorio a procesar.
+/// * `bloque` - Registros crudos del chunk, con la línea en la que empieza cada uno.
+/// * `encabezados` - Encabezados del archivo del chunk.
+/// * `opciones` - Opciones con la política de votos desbordados.
+/// * `visitar` - Función que recibe, por cada registro, la línea del archivo en la que
+///   empieza junto a la reseña, sus votos (`None` si se cuenta sin votos) y el primer
+///   valor inválido de sus columnas opcionales, o el motivo por el que se rechaza. Si
+///   devuelve un error, se deja de recorrer el chunk.
 ///
 /// # Retorna
-/// * `Ok(Vec<EstadisticasParciales>)` con las estadísticas generadas o un error si falló algo.
-pub fn procesar_csv_con_rayon(
-    directorio: String,
-) -> Result<Vec<EstadisticasParciales>, Box<dyn Error>> {
-    let path = Path::new(&directorio);
-    validar_directorio(path)?;
-    let (tx, rx) = mpsc::channel::<Vec<Reseña>>();
-    let productor = spawn_productor_directorio(path, tx);
-    let parciales: Vec<EstadisticasParciales> =
-        rx.into_iter().par_bridge().map(procesar_chunk).collect();
+/// * `Ok(())` si se recorrió todo el chunk, o el primer error de `visitar`.
+fn validar_reseñas<E>(
+    bloque: BloqueCsv,
+    encabezados: &ByteRecord,
+    opciones: &OpcionesProcesamiento,
+    mut visitar: impl FnMut(u64, Result<ReseñaValidada<'_>, Rechazo>) -> Result<(), E>,
+) -> Result<(), E> {
+    parsear_reseñas(bloque, encabezados, |linea, resultado| {
+        let resultado = resultado.and_then(|(reseña, campo_invalido)| {
+            match opciones
+                .votos_desbordados
+                .convertir_votos(&reseña.votos_utiles)
+            {
+                Ok(votos) => Ok((reseña, votos, campo_invalido)),
+                Err(e) => {
+                    let columna = columna_de(encabezados, COLUMNA_VOTOS_UTILES);
+                    Err(Rechazo::desde_error_votos(&e, columna))
+                }
+            }
+        });
 
-    productor.join().unwrap();
-    Ok(parciales)
+        visitar(linea, resultado)
+    })
+}
+
+/// Busca la columna (base 1) de un encabezado.
+fn columna_de(encabezados: &ByteRecord, nombre: &str) -> Option<u64> {
+    encabezados
+        .iter()
+        .position(|encabezado| encabezado == nombre.as_bytes())
+        .map(|indice| indice as u64 + 1)
 }
 
 /// Procesa un chunk de reseñas y genera las estadísticas parciales correspondientes.
 ///
+/// Las filas que no se pueden deserializar, o cuyos votos no son válidos (o no entran en
+/// un `u32` con la política `PoliticaVotosDesbordados::Descartar`), se descartan y quedan
+/// registradas en el reporte de ingesta del archivo. En el modo estricto, en cambio, la
+/// primera de esas filas aborta el procesamiento.
+///
 /// # Argumentos
-/// * `chunk` - Vector de reseñas a analizar.
+/// * `chunk` - Chunk de reseñas a parsear y analizar.
+/// * `opciones` - Opciones de modo estricto y de política de votos desbordados.
+/// * `ganadoras` - En el modo deduplicado, el índice de las copias de cada reseña que se
+///   conservan; el resto se descartan como duplicadas.
+/// * `internador` - Internador compartido de los nombres de juegos e idiomas.
+/// * `registrar` - Función que registra cada reseña aceptada (ver `registrar_reseña`).
 ///
 /// # Retorna
-/// * `EstadisticasParciales` con la información procesada del chunk.
-fn procesar_chunk(chunk: Vec<Reseña>) -> EstadisticasParciales {
+/// * Las `EstadisticasParciales` con la información procesada, o el error de la primera
+///   fila malformada en el modo estricto.
+fn procesar_chunk(
+    chunk: Chunk,
+    opciones: &OpcionesProcesamiento,
+    ganadoras: Option<&IndiceReseñas>,
+    internador: &Internador,
+    registrar: &impl Fn(
+        &mut EstadisticasParciales,
+        &mut CacheInternado<'_>,
+        &Reseña<'_>,
+        Option<Votos>,
+        &OpcionesProcesamiento,
+    ),
+) -> Result<EstadisticasParciales, ErrorIngesta> {
     let mut stats = EstadisticasParciales::default();
-    for reseña in chunk {
-        let juego = reseña.nombre_juego;
-        let idioma = reseña.idioma;
-        let texto = reseña.texto;
-        let votos_resultado = reseña.votos_utiles.parse::<u32>();
-        if let Ok(votos) = votos_resultado {
-            let entry = stats.juegos.entry(juego).or_default();
-            entry.cantidad_total += 1;
-            *entry.por_idioma.entry(idioma.clone()).or_insert(0) += 1;
-            match entry.mejores_reviews.entry(idioma.clone()) {
-                std::collections::hash_map::Entry::Occupied(mut e) => {
-                    if votos > e.get().1 {
-                        e.insert((texto.clone(), votos));
-                    }
+    let mut cache = internador.cache();
+    let mut reporte = ReporteArchivo::default();
+    let registro_invalido = |linea: u64, rechazo: Rechazo| ErrorIngesta::RegistroInvalido {
+        archivo: chunk.ruta.to_string(),
+        linea,
+        rechazo,
+    };
+
+    validar_reseñas(
+        chunk.bloque,
+        &chunk.encabezados,
+        opciones,
+        |linea, resultado| {
+            let (reseña, votos, campo_invalido) = match resultado {
+                Ok(r) => r,
+                Err(rechazo) if opciones.estricto => return Err(registro_invalido(linea, rechazo)),
+                Err(rechazo) => {
+                    reporte.registrar_rechazo(rechazo.motivo, linea);
+                    return Ok(());
                 }
+            };
 
-                std::collections::hash_map::Entry::Vacant(e) => {
-                    e.insert((texto.clone(), votos));
+            let campo_invalido = match campo_invalido {
+                Some(rechazo) if opciones.estricto => {
+                    return Err(registro_invalido(linea, rechazo));
                 }
+                campo_invalido => campo_invalido.is_some(),
+            };
+
+            if let (Some(ganadoras), Some(id)) = (ganadoras, reseña.id_reseña)
+                && !ganadoras.es_ganadora(id, (chunk.orden.0, linea))
+            {
+                reporte.registrar_duplicada();
+                return Ok(());
+            }
+
+            reporte.registrar_aceptada();
+            if campo_invalido {
+                reporte.registrar_campo_invalido(linea);
             }
 
-            let idioma_entry = stats.por_idioma.entry(idioma).or_default();
-            idioma_entry.cantidad_total += 1;
-            idioma_entry.top_reviews.push((texto, votos));
+            registrar(&mut stats, &mut cache, &reseña, votos, opciones);
+            Ok(())
+        },
+    )?;
+
+    stats
+        .ingesta
+        .archivos
+        .insert(chunk.ruta.to_string(), reporte);
+
+    Ok(stats)
+}
+
+/// Registra una reseña aceptada en las estadísticas parciales de un chunk.
+///
+/// El texto de la reseña puede ser prestado: sólo se copia si entra a alguno de los
+/// rankings de reseñas destacadas.
+///
+/// # Argumentos
+/// * `stats` - Estadísticas parciales del chunk.
+/// * `cache` - Cache del internador con el que se guardan los nombres de los juegos y
+///   los idiomas.
+/// * `reseña` - Reseña a registrar.
+/// * `votos` - Votos útiles de la reseña, ya convertidos según la política de votos
+///   desbordados (`None` si se cuenta sin votos).
+/// * `opciones` - Opciones del 'pipeline' de procesamiento.
+pub fn registrar_reseña(
+    stats: &mut EstadisticasParciales,
+    cache: &mut CacheInternado<'_>,
+    reseña: &Reseña<'_>,
+    votos: Option<Votos>,
+    opciones: &OpcionesProcesamiento,
+) {
+    let nombre = cache.internar(&reseña.nombre_juego);
+    let clave = match reseña.id_juego {
+        Some(id) => ClaveJuego::Id(id),
+        None => ClaveJuego::SinId(Arc::clone(&nombre)),
+    };
+
+    let idioma = cache.internar(&reseña.idioma);
+    let entry = stats.juegos.entry(clave).or_default();
+    entry.cantidad_total += 1;
+    entry.registrar_nombre(nombre, reseña.actualizada.or(reseña.creada));
+    *entry.por_idioma.entry(Arc::clone(&idioma)).or_insert(0) += 1;
+    entry.registrar_recomendacion(&idioma, reseña.recomendada);
+    entry.tiempo_de_juego.registrar(
+        reseña.recomendada,
+        reseña.autor_minutos_al_reseñar,
+        reseña.autor_minutos_totales,
+    );
+    entry.fases_lanzamiento.registrar(
+        reseña.acceso_anticipado,
+        reseña.recomendada,
+        votos,
+        opciones
+            .metrica_reviews
+            .valor(reseña, votos)
+            .map(|valor| ReseñaDestacada {
+                texto: reseña.texto.as_ref(),
+                valor,
+                id: reseña.id_reseña,
+            }),
+    );
+    entry.canales_compra.registrar(
+        CanalCompra::desde_reseña(reseña.compra_en_steam, reseña.recibido_gratis),
+        reseña.recomendada,
+    );
+    if let Some(autor) = reseña.autor_id_steam {
+        entry.autores.registrar(opciones.precision_autores, autor);
+        stats.autores.registrar(opciones.precision_autores, autor);
+    }
+
+    let inicio_periodo = opciones
+        .linea_de_tiempo
+        .zip(reseña.creada)
+        .map(|(periodo, creada)| periodo.inicio(creada));
+    if let Some(inicio) = inicio_periodo {
+        entry.linea_de_tiempo.registrar(inicio, reseña.recomendada);
+    }
+
+    // El texto de la reseña es prestado: en cada ranking, se copia sólo si entra al
+    // top del idioma o es la destacada del juego en ese idioma, y se clona sólo si
+    // cumple ambas condiciones.
+    let mut destacadas_idioma = Vec::new();
+    for ranking in RankingReseñas::TODOS {
+        let Some(valor) = ranking.valor(opciones.metrica_reviews, reseña, votos) else {
+            continue;
+        };
+
+        let candidata = ReseñaDestacada {
+            texto: reseña.texto.as_ref(),
+            valor,
+            id: reseña.id_reseña,
+        };
+
+        let entra_al_idioma = stats
+            .por_idioma
+            .get(&idioma)
+            .is_none_or(|info| info.destacadas(ranking).admite(&candidata));
+
+        match (
+            entry.admite_destacada(ranking, &idioma, &candidata),
+            entra_al_idioma,
+        ) {
+            (true, true) => {
+                let destacada = candidata.con_texto_propio();
+                entry.registrar_destacada(ranking, &idioma, destacada.clone());
+                destacadas_idioma.push((ranking, destacada));
+            }
+            (true, false) => {
+                entry.registrar_destacada(ranking, &idioma, candidata.con_texto_propio())
+            }
+            (false, true) => destacadas_idioma.push((ranking, candidata.con_texto_propio())),
+            (false, false) => {}
         }
     }
 
-    stats
+    let idioma_entry = stats
+        .por_idioma
+        .entry(idioma)
+        .or_insert_with(|| InfoIdioma::nuevo(opciones.limites.reviews_por_idioma.capacidad()));
+    idioma_entry.cantidad_total += 1;
+    if let Some(autor) = reseña.autor_id_steam {
+        idioma_entry
+            .autores
+            .registrar(opciones.precision_autores, autor);
+    }
+    if let Some(inicio) = inicio_periodo {
+        idioma_entry
+            .linea_de_tiempo
+            .registrar(inicio, reseña.recomendada);
+    }
+    for (ranking, destacada) in destacadas_idioma {
+        idioma_entry.destacadas_mut(ranking).insertar(destacada);
+    }
 }
@@ -0,0 +1,370 @@
+//! Este módulo contiene el reporte de ingesta: cuántas filas se leyeron, aceptaron
+//! y rechazaron de cada archivo, y por qué motivo.
+
+// Imports de crates externas.
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::num::{IntErrorKind, ParseIntError};
+
+// Constantes.
+const MAX_LINEAS_POR_MOTIVO: usize = 10;
+const PREFIJO_CAMPO_FALTANTE: &str = "missing field";
+
+// Mensajes.
+const TITULO_REPORTE: &str = "📋 Reporte de ingesta:";
+const MSJ_FILAS_LEIDAS: &str = "filas leídas,";
+const MSJ_FILAS_ACEPTADAS: &str = "aceptadas,";
+const MSJ_FILAS_RECHAZADAS: &str = "rechazadas";
+const MSJ_FILAS_DUPLICADAS: &str = "duplicadas";
+const MSJ_PRIMERAS_LINEAS: &str = "primeras líneas:";
+const MSJ_ACEPTADAS_SIN_VALOR: &str = "aceptadas sin el valor inválido";
+const ERROR_REGISTRO_INVALIDO: &str = "Registro inválido en";
+const ERROR_ARCHIVO_ILEGIBLE: &str = "No se pudo leer el archivo";
+const ERROR_ENTRADA_ILEGIBLE: &str = "No se pudo leer una entrada del directorio";
+const MSJ_LINEA: &str = "línea";
+const MSJ_COLUMNA: &str = "columna";
+const MSJ_CAMPOS_ESPERADOS: &str = "se esperaban";
+const MSJ_CAMPOS_ENCONTRADOS: &str = "campos y el registro tiene";
+
+/// Motivos por los que se puede rechazar una fila del `.csv`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MotivoRechazo {
+    /// El registro no se pudo leer o deserializar por un error de formato.
+    ErrorCsv,
+    /// Algún campo usado no es UTF-8 válido.
+    Utf8Invalido,
+    /// El registro tiene menos campos que el encabezado, o le falta alguna columna obligatoria.
+    ColumnaFaltante,
+    /// Algún campo tiene un valor que no corresponde a su tipo. Como todas las columnas
+    /// tipadas son opcionales, fuera del modo estricto la fila no se rechaza: se cuenta sin
+    /// ese valor y se informa aparte (ver `ReporteArchivo::campos_invalidos`).
+    CampoInvalido,
+    /// `votes_helpful` es numérico pero no entra en un `u32` (o en un `u64`, con la
+    /// política de votos desbordados que los amplía).
+    VotosDesbordados,
+    /// `votes_helpful` no es un número entero positivo.
+    VotosNoNumericos,
+}
+
+impl MotivoRechazo {
+    /// Clasifica un error de lectura o deserialización de la crate `csv`.
+    pub fn desde_error_csv(error: &csv::Error) -> Self {
+        match error.kind() {
+            csv::ErrorKind::Utf8 { .. } => MotivoRechazo::Utf8Invalido,
+            csv::ErrorKind::Deserialize { err, .. } => match err.kind() {
+                csv::DeserializeErrorKind::InvalidUtf8(_) => MotivoRechazo::Utf8Invalido,
+                csv::DeserializeErrorKind::UnexpectedEndOfRow => MotivoRechazo::ColumnaFaltante,
+                csv::DeserializeErrorKind::Message(mensaje)
+                    if mensaje.starts_with(PREFIJO_CAMPO_FALTANTE) =>
+                {
+                    MotivoRechazo::ColumnaFaltante
+                }
+                csv::DeserializeErrorKind::Message(_)
+                | csv::DeserializeErrorKind::ParseBool(_)
+                | csv::DeserializeErrorKind::ParseInt(_)
+                | csv::DeserializeErrorKind::ParseFloat(_) => MotivoRechazo::CampoInvalido,
+                _ => MotivoRechazo::ErrorCsv,
+            },
+            _ => MotivoRechazo::ErrorCsv,
+        }
+    }
+
+    /// Clasifica el error al convertir `votes_helpful` a número.
+    pub fn desde_error_votos(error: &ParseIntError) -> Self {
+        match error.kind() {
+            IntErrorKind::PosOverflow => MotivoRechazo::VotosDesbordados,
+            _ => MotivoRechazo::VotosNoNumericos,
+        }
+    }
+
+    /// Nombre del motivo, tal como se muestra en el reporte.
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            MotivoRechazo::ErrorCsv => "csv_error",
+            MotivoRechazo::Utf8Invalido => "invalid_utf8",
+            MotivoRechazo::ColumnaFaltante => "missing_column",
+            MotivoRechazo::CampoInvalido => "invalid_field",
+            MotivoRechazo::VotosDesbordados => "vote_overflow",
+            MotivoRechazo::VotosNoNumericos => "non_numeric_vote",
+        }
+    }
+}
+
+/// Detalle de una fila rechazada.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rechazo {
+    pub motivo: MotivoRechazo,
+    /// Columna (base 1) del campo que causó el rechazo, si se conoce.
+    pub columna: Option<u64>,
+    /// Descripción del error original.
+    pub detalle: String,
+}
+
+impl Rechazo {
+    /// Arma el rechazo a partir de un error de lectura o deserialización de la crate `csv`.
+    pub fn desde_error_csv(error: &csv::Error) -> Self {
+        let (columna, detalle) = match error.kind() {
+            csv::ErrorKind::Utf8 { err, .. } => (Some(err.field() as u64), err.to_string()),
+            csv::ErrorKind::Deserialize { err, .. } => (err.field(), err.kind().to_string()),
+            _ => (None, error.to_string()),
+        };
+
+        Rechazo {
+            motivo: MotivoRechazo::desde_error_csv(error),
+            columna: columna.map(|indice| indice + 1),
+            detalle,
+        }
+    }
+
+    /// Arma el rechazo de un registro con menos campos que el encabezado de su archivo.
+    ///
+    /// # Argumentos
+    /// * `esperados` - Cantidad de columnas del encabezado.
+    /// * `encontrados` - Cantidad de campos del registro.
+    pub fn campos_faltantes(esperados: usize, encontrados: usize) -> Self {
+        Rechazo {
+            motivo: MotivoRechazo::ColumnaFaltante,
+            columna: Some(encontrados as u64 + 1),
+            detalle: format!(
+                "{} {} {} {}",
+                MSJ_CAMPOS_ESPERADOS, esperados, MSJ_CAMPOS_ENCONTRADOS, encontrados
+            ),
+        }
+    }
+
+    /// Arma el rechazo a partir del error al convertir `votes_helpful` a número.
+    ///
+    /// # Argumentos
+    /// * `error` - Error de la conversión.
+    /// * `columna` - Columna (base 1) de `votes_helpful` en el archivo, si se conoce.
+    pub fn desde_error_votos(error: &ParseIntError, columna: Option<u64>) -> Self {
+        Rechazo {
+            motivo: MotivoRechazo::desde_error_votos(error),
+            columna,
+            detalle: error.to_string(),
+        }
+    }
+}
+
+/// Errores que abortan la ingesta en el modo estricto.
+#[derive(Debug)]
+pub enum ErrorIngesta {
+    /// Una fila del archivo no se pudo deserializar o validar.
+    RegistroInvalido {
+        archivo: String,
+        linea: u64,
+        rechazo: Rechazo,
+    },
+    /// No se pudo abrir o leer un archivo.
+    ArchivoIlegible { archivo: String, detalle: String },
+    /// No se pudo leer una entrada de un directorio.
+    EntradaIlegible { directorio: String, detalle: String },
+}
+
+impl fmt::Display for ErrorIngesta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorIngesta::RegistroInvalido {
+                archivo,
+                linea,
+                rechazo,
+            } => {
+                write!(
+                    f,
+                    "{} '{}', {} {}",
+                    ERROR_REGISTRO_INVALIDO, archivo, MSJ_LINEA, linea
+                )?;
+                if let Some(columna) = rechazo.columna {
+                    write!(f, ", {} {}", MSJ_COLUMNA, columna)?;
+                }
+
+                write!(f, " ({}): {}", rechazo.motivo.nombre(), rechazo.detalle)
+            }
+
+            ErrorIngesta::ArchivoIlegible { archivo, detalle } => {
+                write!(f, "{} '{}': {}", ERROR_ARCHIVO_ILEGIBLE, archivo, detalle)
+            }
+
+            ErrorIngesta::EntradaIlegible {
+                directorio,
+                detalle,
+            } => {
+                write!(
+                    f,
+                    "{} '{}': {}",
+                    ERROR_ENTRADA_ILEGIBLE, directorio, detalle
+                )
+            }
+        }
+    }
+}
+
+impl Error for ErrorIngesta {}
+
+/// Filas de un archivo informadas por un mismo motivo.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FilasPorMotivo {
+    pub cantidad: u64,
+    /// Primeras `MAX_LINEAS_POR_MOTIVO` líneas informadas, ordenadas de menor a mayor.
+    pub primeras_lineas: Vec<u64>,
+}
+
+/// Resultado de la ingesta de un archivo.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReporteArchivo {
+    pub filas_leidas: u64,
+    pub filas_aceptadas: u64,
+    /// Filas válidas descartadas por ser copias de otra reseña (modo deduplicado).
+    pub filas_duplicadas: u64,
+    pub rechazos: BTreeMap<MotivoRechazo, FilasPorMotivo>,
+    /// Filas aceptadas con algún valor inválido en una columna opcional, que se cuenta
+    /// como vacío.
+    pub campos_invalidos: FilasPorMotivo,
+}
+
+/// Reporte de ingesta de todos los archivos procesados, indexado por ruta.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReporteIngesta {
+    pub archivos: BTreeMap<String, ReporteArchivo>,
+}
+
+impl FilasPorMotivo {
+    /// Registra una fila, junto a la línea del archivo en la que empieza.
+    fn registrar(&mut self, linea: u64) {
+        self.cantidad += 1;
+        if self.primeras_lineas.len() < MAX_LINEAS_POR_MOTIVO {
+            self.agregar_lineas(&[linea]);
+        }
+    }
+
+    /// Fusiona las filas de `self` en `destino`.
+    fn merge_into(self, destino: &mut FilasPorMotivo) {
+        destino.cantidad += self.cantidad;
+        destino.agregar_lineas(&self.primeras_lineas);
+    }
+
+    /// Primeras líneas informadas, separadas por comas.
+    fn lineas(&self) -> String {
+        let lineas: Vec<String> = self
+            .primeras_lineas
+            .iter()
+            .map(|linea| linea.to_string())
+            .collect();
+
+        lineas.join(", ")
+    }
+
+    /// Agrega las líneas recibidas manteniendo sólo las `MAX_LINEAS_POR_MOTIVO` menores.
+    fn agregar_lineas(&mut self, lineas: &[u64]) {
+        self.primeras_lineas.extend_from_slice(lineas);
+        self.primeras_lineas.sort_unstable();
+        self.primeras_lineas.truncate(MAX_LINEAS_POR_MOTIVO);
+    }
+}
+
+impl ReporteArchivo {
+    /// Registra una fila aceptada.
+    pub fn registrar_aceptada(&mut self) {
+        self.filas_leidas += 1;
+        self.filas_aceptadas += 1;
+    }
+
+    /// Registra una fila rechazada, junto a la línea del archivo en la que empieza.
+    pub fn registrar_rechazo(&mut self, motivo: MotivoRechazo, linea: u64) {
+        self.filas_leidas += 1;
+        self.rechazos.entry(motivo).or_default().registrar(linea);
+    }
+
+    /// Registra que una fila aceptada tiene algún valor inválido en una columna opcional.
+    /// La fila se registra aparte como aceptada.
+    pub fn registrar_campo_invalido(&mut self, linea: u64) {
+        self.campos_invalidos.registrar(linea);
+    }
+
+    /// Registra una fila válida descartada por ser una copia de otra reseña.
+    pub fn registrar_duplicada(&mut self) {
+        self.filas_leidas += 1;
+        self.filas_duplicadas += 1;
+    }
+
+    /// Cantidad total de filas rechazadas.
+    pub fn filas_rechazadas(&self) -> u64 {
+        self.filas_leidas - self.filas_aceptadas - self.filas_duplicadas
+    }
+
+    /// Fusiona el reporte de `self` en `destino`.
+    fn merge_into(self, destino: &mut ReporteArchivo) {
+        destino.filas_leidas += self.filas_leidas;
+        destino.filas_aceptadas += self.filas_aceptadas;
+        destino.filas_duplicadas += self.filas_duplicadas;
+        for (motivo, rechazos) in self.rechazos {
+            rechazos.merge_into(destino.rechazos.entry(motivo).or_default());
+        }
+
+        self.campos_invalidos
+            .merge_into(&mut destino.campos_invalidos);
+    }
+}
+
+impl ReporteIngesta {
+    /// Fusiona el reporte de `self` en `destino`.
+    pub fn merge_into(self, destino: &mut ReporteIngesta) {
+        for (ruta, reporte) in self.archivos {
+            reporte.merge_into(destino.archivos.entry(ruta).or_default());
+        }
+    }
+
+    /// Cantidad total de filas descartadas por duplicadas, en todos los archivos.
+    pub fn filas_duplicadas(&self) -> u64 {
+        self.archivos
+            .values()
+            .map(|reporte| reporte.filas_duplicadas)
+            .sum()
+    }
+
+    /// Imprime el reporte por `stderr`.
+    pub fn imprimir(&self) {
+        eprintln!("{}", TITULO_REPORTE);
+        for (ruta, reporte) in &self.archivos {
+            let duplicadas = if reporte.filas_duplicadas > 0 {
+                format!(", {} {}", reporte.filas_duplicadas, MSJ_FILAS_DUPLICADAS)
+            } else {
+                String::new()
+            };
+
+            eprintln!(
+                "  {}: {} {} {} {} {} {}{}.",
+                ruta,
+                reporte.filas_leidas,
+                MSJ_FILAS_LEIDAS,
+                reporte.filas_aceptadas,
+                MSJ_FILAS_ACEPTADAS,
+                reporte.filas_rechazadas(),
+                MSJ_FILAS_RECHAZADAS,
+                duplicadas,
+            );
+
+            for (motivo, rechazos) in &reporte.rechazos {
+                eprintln!(
+                    "    - {}: {} ({} {})",
+                    motivo.nombre(),
+                    rechazos.cantidad,
+                    MSJ_PRIMERAS_LINEAS,
+                    rechazos.lineas(),
+                );
+            }
+
+            if reporte.campos_invalidos.cantidad > 0 {
+                eprintln!(
+                    "    - {} ({}): {} ({} {})",
+                    MotivoRechazo::CampoInvalido.nombre(),
+                    MSJ_ACEPTADAS_SIN_VALOR,
+                    reporte.campos_invalidos.cantidad,
+                    MSJ_PRIMERAS_LINEAS,
+                    reporte.campos_invalidos.lineas(),
+                );
+            }
+        }
+    }
+}
@@ -1,29 +1,213 @@
-//! Este módulo contiene la estructura usada para el parseo incial del '.csv'.
-
-// Imports de crates externas.
-use serde::Deserialize;
-
-/// Estructura que representa una reseña individual de un archivo `.csv`.
-///
-/// Esta estructura se usa para deserializar directamente los campos del CSV,
-/// renombrando los encabezados originales a nombres más descriptivos en español.
-#[derive(Debug, Deserialize, Clone)]
-pub struct Reseña {
-    /// Nombre del juego (`app_name` en el CSV original).
-    #[serde(rename = "app_name")]
-    pub nombre_juego: String,
-
-    /// Idioma en el que está escrita la reseña (`language`).
-    #[serde(rename = "language")]
-    pub idioma: String,
-
-    /// Texto completo de la reseña (`review`).
-    #[serde(rename = "review")]
-    pub texto: String,
-
-    /// Cantidad de votos útiles recibidos (`votes_helpful`).
-    ///
-    /// Se mantiene como `String` al parsearse y se convierte a `u32` más adelante.
-    #[serde(rename = "votes_helpful")]
-    pub votos_utiles: String,
-}
+//! Este módulo contiene la estructura usada para el parseo incial del '.csv'.
+
+// Imports de crates externas.
+use serde::de::{Error, Unexpected};
+use serde::{Deserialize, Deserializer};
+use std::borrow::Cow;
+
+// Constantes.
+const VALORES_VERDADEROS: [&str; 2] = ["True", "true"];
+const VALORES_FALSOS: [&str; 2] = ["False", "false"];
+
+// Mensajes.
+const ESPERADO_BOOLEANO: &str = "'True' o 'False'";
+const ESPERADO_MARCA_TEMPORAL: &str = "una marca temporal Unix en segundos";
+
+/// Marca temporal Unix, en segundos.
+pub type MarcaTemporal = i64;
+
+/// Estructura que representa una reseña individual de un archivo `.csv`.
+///
+/// Esta estructura se usa para deserializar directamente los campos del CSV,
+/// renombrando los encabezados originales a nombres más descriptivos en español.
+///
+/// Los campos usados por el análisis original son obligatorios; el resto son
+/// opcionales, y valen `None` si el campo está vacío o si el archivo no tiene esa
+/// columna. Un valor presente pero con un tipo inválido hace fallar la deserialización
+/// (al procesar los `.csv`, ese campo se cuenta como vacío y se informa en el reporte de
+/// ingesta). La primera columna del dataset (el índice, sin nombre) se ignora.
+///
+/// Se deserializan todas las columnas aunque ningún análisis use algunas de ellas
+/// todavía, para poder construir nuevos análisis sin volver a parsear el `.csv`.
+///
+/// Los campos de texto se toman prestados del registro del que se deserializa la reseña
+/// (`'a`), por lo que parsear una fila no reserva memoria para ellos: el texto de una
+/// reseña se copia recién si entra en alguno de los tops.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Reseña<'a> {
+    /// Identificador del juego en Steam (`app_id`).
+    #[serde(rename = "app_id", default)]
+    pub id_juego: Option<u64>,
+
+    /// Nombre del juego (`app_name` en el CSV original).
+    #[serde(rename = "app_name", borrow)]
+    pub nombre_juego: Cow<'a, str>,
+
+    /// Identificador de la reseña (`review_id`).
+    #[serde(rename = "review_id", default)]
+    pub id_reseña: Option<u64>,
+
+    /// Idioma en el que está escrita la reseña (`language`).
+    #[serde(rename = "language", borrow)]
+    pub idioma: Cow<'a, str>,
+
+    /// Texto completo de la reseña (`review`).
+    #[serde(rename = "review", borrow)]
+    pub texto: Cow<'a, str>,
+
+    /// Fecha de creación de la reseña (`timestamp_created`).
+    #[serde(
+        rename = "timestamp_created",
+        default,
+        deserialize_with = "deserializar_marca_temporal"
+    )]
+    pub creada: Option<MarcaTemporal>,
+
+    /// Fecha de la última modificación de la reseña (`timestamp_updated`).
+    #[serde(
+        rename = "timestamp_updated",
+        default,
+        deserialize_with = "deserializar_marca_temporal"
+    )]
+    pub actualizada: Option<MarcaTemporal>,
+
+    /// Si el autor recomienda el juego (`recommended`).
+    #[serde(
+        rename = "recommended",
+        default,
+        deserialize_with = "deserializar_booleano"
+    )]
+    pub recomendada: Option<bool>,
+
+    /// Cantidad de votos útiles recibidos (`votes_helpful`).
+    ///
+    /// Se mantiene como texto al parsearse y se convierte a número más adelante, según
+    /// la política de votos desbordados.
+    #[serde(rename = "votes_helpful", borrow)]
+    pub votos_utiles: Cow<'a, str>,
+
+    /// Cantidad de votos graciosos recibidos (`votes_funny`).
+    #[serde(rename = "votes_funny", default)]
+    pub votos_graciosos: Option<u64>,
+
+    /// Puntaje de utilidad ponderado calculado por Steam (`weighted_vote_score`).
+    #[serde(rename = "weighted_vote_score", default)]
+    pub puntaje_ponderado: Option<f64>,
+
+    /// Cantidad de comentarios de la reseña (`comment_count`).
+    #[serde(rename = "comment_count", default)]
+    pub cantidad_comentarios: Option<u64>,
+
+    /// Si el autor compró el juego en Steam (`steam_purchase`).
+    #[serde(
+        rename = "steam_purchase",
+        default,
+        deserialize_with = "deserializar_booleano"
+    )]
+    pub compra_en_steam: Option<bool>,
+
+    /// Si el autor recibió el juego gratis (`received_for_free`).
+    #[serde(
+        rename = "received_for_free",
+        default,
+        deserialize_with = "deserializar_booleano"
+    )]
+    pub recibido_gratis: Option<bool>,
+
+    /// Si la reseña se escribió durante el acceso anticipado (`written_during_early_access`).
+    #[serde(
+        rename = "written_during_early_access",
+        default,
+        deserialize_with = "deserializar_booleano"
+    )]
+    pub acceso_anticipado: Option<bool>,
+
+    /// Identificador de Steam del autor (`author.steamid`).
+    #[serde(rename = "author.steamid", default)]
+    pub autor_id_steam: Option<u64>,
+
+    /// Cantidad de juegos del autor (`author.num_games_owned`).
+    #[allow(dead_code)]
+    #[serde(rename = "author.num_games_owned", default)]
+    pub autor_cantidad_juegos: Option<u64>,
+
+    /// Cantidad de reseñas escritas por el autor (`author.num_reviews`).
+    #[allow(dead_code)]
+    #[serde(rename = "author.num_reviews", default)]
+    pub autor_cantidad_reseñas: Option<u64>,
+
+    /// Minutos jugados por el autor en total (`author.playtime_forever`).
+    #[serde(rename = "author.playtime_forever", default)]
+    pub autor_minutos_totales: Option<f64>,
+
+    /// Minutos jugados por el autor en las últimas dos semanas
+    /// (`author.playtime_last_two_weeks`).
+    #[allow(dead_code)]
+    #[serde(rename = "author.playtime_last_two_weeks", default)]
+    pub autor_minutos_ultimas_semanas: Option<f64>,
+
+    /// Minutos jugados por el autor al escribir la reseña (`author.playtime_at_review`).
+    #[serde(rename = "author.playtime_at_review", default)]
+    pub autor_minutos_al_reseñar: Option<f64>,
+
+    /// Última vez que jugó el autor (`author.last_played`).
+    #[allow(dead_code)]
+    #[serde(
+        rename = "author.last_played",
+        default,
+        deserialize_with = "deserializar_marca_temporal"
+    )]
+    pub autor_ultima_partida: Option<MarcaTemporal>,
+}
+
+/// Deserializa un booleano con el formato del dataset (`True`/`False`).
+///
+/// Un campo vacío se deserializa como `None`.
+fn deserializar_booleano<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(valor) = Option::<&str>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+
+    if VALORES_VERDADEROS.contains(&valor) {
+        Ok(Some(true))
+    } else if VALORES_FALSOS.contains(&valor) {
+        Ok(Some(false))
+    } else {
+        Err(D::Error::invalid_value(
+            Unexpected::Str(valor),
+            &ESPERADO_BOOLEANO,
+        ))
+    }
+}
+
+/// Deserializa una marca temporal Unix en segundos.
+///
+/// Algunas columnas del dataset guardan las marcas temporales como números de punto
+/// flotante (`1611343383.0`), por lo que se aceptan ambos formatos. Un campo vacío se
+/// deserializa como `None`.
+fn deserializar_marca_temporal<'de, D>(deserializer: D) -> Result<Option<MarcaTemporal>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(valor) = Option::<&str>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+
+    if let Ok(segundos) = valor.parse::<MarcaTemporal>() {
+        return Ok(Some(segundos));
+    }
+
+    match valor.parse::<f64>() {
+        Ok(segundos) if segundos.is_finite() && segundos.fract() == 0.0 => {
+            Ok(Some(segundos as MarcaTemporal))
+        }
+
+        _ => Err(D::Error::invalid_value(
+            Unexpected::Str(valor),
+            &ESPERADO_MARCA_TEMPORAL,
+        )),
+    }
+}
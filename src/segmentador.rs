@@ -0,0 +1,265 @@
+//! Este módulo contiene la lógica de división de un `.csv` en bloques de bytes
+//! alineados a registros, para que cada bloque pueda parsearse en un hilo distinto.
+
+// Imports de crates externas.
+use csv::{ByteRecord, ReaderBuilder};
+use memchr::memchr3;
+use std::io::{self, Read};
+
+// Constantes.
+const TAMAÑO_LECTURA: usize = 64 * 1024;
+
+/// Estados posibles del recorrido de un `.csv` byte a byte.
+///
+/// Replica la máquina de estados de `csv-core` (con comillas dobles como escape y
+/// `\n`, `\r` o `\r\n` como fin de registro) lo justo y necesario para saber dónde
+/// empieza cada registro, sin confundir los saltos de línea de un campo entre comillas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EstadoCsv {
+    InicioRegistro,
+    InicioCampo,
+    EnCampo,
+    EnCampoEntreComillas,
+    ComillaEnCampoEntreComillas,
+}
+
+impl EstadoCsv {
+    /// Avanza la máquina de estados con el byte recibido.
+    ///
+    /// Igual que en `csv-core`, los fines de línea al inicio de un registro (como el `\n`
+    /// de un `\r\n`, o las líneas vacías) se descartan.
+    ///
+    /// # Retorna
+    /// * `true` si el byte es el primero de un registro.
+    fn avanzar(&mut self, byte: u8) -> bool {
+        use EstadoCsv::*;
+        let (siguiente, inicio_registro) = match (*self, byte) {
+            (EnCampoEntreComillas, b'"') => (ComillaEnCampoEntreComillas, false),
+            (EnCampoEntreComillas, _) => (EnCampoEntreComillas, false),
+            (ComillaEnCampoEntreComillas, b'"') => (EnCampoEntreComillas, false),
+            (InicioRegistro, b'\r' | b'\n') => (InicioRegistro, false),
+            (InicioRegistro, b'"') => (EnCampoEntreComillas, true),
+            (InicioRegistro, b',') => (InicioCampo, true),
+            (InicioRegistro, _) => (EnCampo, true),
+            (InicioCampo, b'"') => (EnCampoEntreComillas, false),
+            (_, b',') => (InicioCampo, false),
+            (_, b'\r' | b'\n') => (InicioRegistro, false),
+            _ => (EnCampo, false),
+        };
+
+        *self = siguiente;
+        inicio_registro
+    }
+}
+
+/// Criterio con el que se decide el tamaño de cada bloque.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TamañoChunk {
+    /// Cantidad máxima de registros por bloque.
+    Filas(usize),
+    /// Cantidad aproximada de bytes por bloque (se corta en el primer inicio de registro
+    /// que alcanza el tamaño pedido).
+    Bytes(usize),
+}
+
+impl TamañoChunk {
+    /// Indica si un bloque con `filas` registros y `bytes` bytes ya alcanzó el tamaño pedido.
+    fn alcanzado(&self, filas: usize, bytes: usize) -> bool {
+        match *self {
+            TamañoChunk::Filas(maximo) => filas >= maximo,
+            TamañoChunk::Bytes(maximo) => bytes >= maximo,
+        }
+    }
+}
+
+/// Bloque de registros completos de un `.csv` (sin encabezado).
+#[derive(Debug)]
+pub struct BloqueCsv {
+    /// Número de línea (base 1) del archivo en el que empieza cada registro del bloque.
+    pub lineas: Vec<u64>,
+    /// Bytes crudos de los registros del bloque.
+    pub datos: Vec<u8>,
+}
+
+/// Iterador que lee un `.csv` y lo corta en bloques del tamaño pedido, sin partir
+/// nunca un campo entre comillas que contenga saltos de línea.
+pub struct Segmentador<R: Read> {
+    lector: R,
+    encabezados: ByteRecord,
+    tamaño: TamañoChunk,
+    buffer: Vec<u8>,
+    escaneado: usize,
+    /// Línea (base 1) de cada registro del bloque en curso.
+    lineas: Vec<u64>,
+    /// Línea (base 1) del byte en la posición `escaneado`.
+    linea: u64,
+    estado: EstadoCsv,
+    terminado: bool,
+}
+
+impl<R: Read> Segmentador<R> {
+    /// Crea el segmentador leyendo el encabezado del `.csv`.
+    ///
+    /// # Argumentos
+    /// * `lector` - Fuente de bytes del `.csv`.
+    /// * `tamaño` - Tamaño de cada bloque, en registros o en bytes.
+    ///
+    /// # Retorna
+    /// * `Ok(None)` si la fuente no tiene ningún registro (ni siquiera el encabezado).
+    pub fn nuevo(lector: R, tamaño: TamañoChunk) -> io::Result<Option<Self>> {
+        let mut segmentador = Segmentador {
+            lector,
+            encabezados: ByteRecord::new(),
+            tamaño,
+            buffer: Vec::new(),
+            escaneado: 0,
+            lineas: Vec::new(),
+            linea: 1,
+            estado: EstadoCsv::InicioRegistro,
+            terminado: false,
+        };
+
+        // Las líneas vacías previas al encabezado se descartan igual que lo hace `csv`.
+        while let Some(bloque) = segmentador.siguiente_bloque(TamañoChunk::Filas(1))? {
+            let mut lector_encabezado = ReaderBuilder::new()
+                .has_headers(false)
+                .flexible(true)
+                .from_reader(bloque.datos.as_slice());
+
+            if lector_encabezado.read_byte_record(&mut segmentador.encabezados)? {
+                return Ok(Some(segmentador));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Devuelve los encabezados del `.csv`.
+    pub fn encabezados(&self) -> &ByteRecord {
+        &self.encabezados
+    }
+
+    /// Lee más bytes de la fuente al buffer interno.
+    ///
+    /// # Retorna
+    /// * `Ok(false)` si la fuente ya no tiene más datos.
+    fn leer_mas(&mut self) -> io::Result<bool> {
+        let inicio = self.buffer.len();
+        self.buffer.resize(inicio + TAMAÑO_LECTURA, 0);
+        let leidos = loop {
+            match self.lector.read(&mut self.buffer[inicio..]) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.buffer.truncate(inicio);
+                    return Err(e);
+                }
+            }
+        };
+
+        self.buffer.truncate(inicio + leidos);
+        Ok(leidos > 0)
+    }
+
+    /// Corta el buffer en la posición escaneada y arma el bloque correspondiente.
+    ///
+    /// Sólo se copian los bytes ya leídos que quedan después del corte (a lo sumo una
+    /// lectura), a un buffer nuevo con lugar para un bloque del mismo tamaño, para que no
+    /// se tenga que agrandar (copiando lo leído) mientras se llena.
+    fn cortar_bloque(&mut self) -> BloqueCsv {
+        let capacidad = if self.terminado {
+            0
+        } else {
+            self.escaneado + TAMAÑO_LECTURA
+        };
+
+        let mut resto = Vec::with_capacity(capacidad);
+        resto.extend_from_slice(&self.buffer[self.escaneado..]);
+        self.buffer.truncate(self.escaneado);
+        self.escaneado = 0;
+        BloqueCsv {
+            lineas: std::mem::take(&mut self.lineas),
+            datos: std::mem::replace(&mut self.buffer, resto),
+        }
+    }
+
+    /// Avanza hasta el próximo byte que puede cambiar el estado o terminar una línea, sin
+    /// recorrer el resto byte a byte.
+    ///
+    /// Dentro de un campo sólo importan las comillas y los fines de línea: una coma no
+    /// cambia nada salvo que la siga una comilla, que en ese caso abre un campo entre
+    /// comillas, por lo que basta con mirar el byte anterior a cada comilla.
+    fn saltear_contenido(&mut self) {
+        use EstadoCsv::*;
+        if !matches!(self.estado, InicioCampo | EnCampo | EnCampoEntreComillas) {
+            return;
+        }
+
+        let resto = &self.buffer[self.escaneado..];
+        let Some(salto) = memchr3(b'"', b'\n', b'\r', resto) else {
+            self.escaneado = self.buffer.len();
+            return;
+        };
+
+        self.escaneado += salto;
+        if self.estado != EnCampoEntreComillas && self.buffer[self.escaneado] == b'"' {
+            self.estado = match self.buffer[self.escaneado - 1] {
+                b',' => InicioCampo,
+                _ => EnCampo,
+            };
+        }
+    }
+
+    /// Avanza sobre la fuente hasta juntar un bloque de registros completos del tamaño
+    /// pedido (o hasta el final).
+    ///
+    /// Los bloques se cortan justo antes del primer byte de un registro, por lo que cada
+    /// bloque empieza en la línea de su primer registro y nunca separa un `\r\n`.
+    fn siguiente_bloque(&mut self, tamaño: TamañoChunk) -> io::Result<Option<BloqueCsv>> {
+        loop {
+            self.saltear_contenido();
+            while self.escaneado < self.buffer.len() {
+                let byte = self.buffer[self.escaneado];
+                let anterior = self.escaneado.checked_sub(1).map(|i| self.buffer[i]);
+                self.linea += u64::from(es_salto_de_linea(anterior, byte));
+                if self.estado.avanzar(byte) {
+                    let filas = self.lineas.len();
+                    if filas > 0 && tamaño.alcanzado(filas, self.escaneado) {
+                        // El byte ya recorrido queda como el primero del bloque siguiente.
+                        let bloque = self.cortar_bloque();
+                        self.lineas.push(self.linea);
+                        self.escaneado = 1;
+                        return Ok(Some(bloque));
+                    }
+
+                    self.lineas.push(self.linea);
+                }
+
+                self.escaneado += 1;
+                self.saltear_contenido();
+            }
+
+            if self.terminado || !self.leer_mas()? {
+                self.terminado = true;
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+
+                return Ok(Some(self.cortar_bloque()));
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for Segmentador<R> {
+    type Item = io::Result<BloqueCsv>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.siguiente_bloque(self.tamaño).transpose()
+    }
+}
+
+/// Indica si un byte termina una línea, contando un `\r\n` como un único salto.
+fn es_salto_de_linea(anterior: Option<u8>, byte: u8) -> bool {
+    byte == b'\r' || (byte == b'\n' && anterior != Some(b'\r'))
+}
@@ -0,0 +1,69 @@
+//! Este módulo contiene los tests de la comparación de las reseñas escritas durante el
+//! acceso anticipado de cada juego y después de su lanzamiento.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::estadisticas_serializables::{FasesJuego, OpcionesSalida};
+    use crate::metricas::ValorMetrica;
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::{preparar_salida_final, procesar_archivo_con_pool};
+
+    const RUTA_DATASET: &str = "dataset_test_acceso_anticipado";
+
+    /// 'Helper' que devuelve las fases de lanzamiento de los juegos del top de
+    /// `dataset_test_acceso_anticipado`.
+    fn fases() -> Vec<FasesJuego> {
+        let opciones = OpcionesProcesamiento::default();
+        let conteo = procesar_archivo_con_pool(&[RUTA_DATASET.to_string()], 2, &opciones);
+        preparar_salida_final(&conteo, &opciones, &OpcionesSalida::default())
+            .top_games
+            .into_iter()
+            .map(|juego| juego.release_phases)
+            .collect()
+    }
+
+    /// 'Test' que verifica la cantidad de reseñas, las recomendaciones, el promedio de
+    /// votos útiles y la mejor reseña de cada fase, y la diferencia entre ambas.
+    ///
+    /// El juego 10 tiene 2 reseñas del acceso anticipado (1 positiva, con 4 y 0 votos), 3
+    /// posteriores (2 positivas, con 10, 2 y 3 votos) y una sin fase, que no se cuenta.
+    #[test]
+    fn test_fases_de_lanzamiento() {
+        let juego = &fases()[0];
+
+        let anticipado = &juego.early_access;
+        assert_eq!(anticipado.review_count, 2);
+        assert_eq!(anticipado.recommendations.positive_percentage, Some(50.0));
+        assert_eq!(anticipado.average_helpful_votes, Some(2.0));
+        let mejor = anticipado.best_review.as_ref().unwrap();
+        assert_eq!(mejor.review, "Temprana buena");
+        assert_eq!(mejor.votes, ValorMetrica::Entero(4));
+
+        let lanzamiento = &juego.post_release;
+        assert_eq!(lanzamiento.review_count, 3);
+        assert_eq!(lanzamiento.recommendations.positive_percentage, Some(66.67));
+        assert_eq!(lanzamiento.average_helpful_votes, Some(5.0));
+        let mejor = lanzamiento.best_review.as_ref().unwrap();
+        assert_eq!(mejor.review, "Lanzada A");
+        assert_eq!(mejor.votes, ValorMetrica::Entero(10));
+
+        assert_eq!(juego.difference.review_count, 1);
+        assert_eq!(juego.difference.positive_percentage, Some(16.67));
+        assert_eq!(juego.difference.average_helpful_votes, Some(3.0));
+    }
+
+    /// 'Test' que verifica que un juego sin reseñas del acceso anticipado no tiene
+    /// promedio, mejor reseña ni diferencias en esa fase.
+    #[test]
+    fn test_juego_sin_acceso_anticipado() {
+        let juego = &fases()[1];
+        assert_eq!(juego.early_access.review_count, 0);
+        assert_eq!(juego.early_access.average_helpful_votes, None);
+        assert!(juego.early_access.best_review.is_none());
+        assert_eq!(juego.post_release.review_count, 1);
+        assert_eq!(juego.difference.review_count, 1);
+        assert_eq!(juego.difference.positive_percentage, None);
+        assert_eq!(juego.difference.average_helpful_votes, None);
+    }
+}
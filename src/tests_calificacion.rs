@@ -0,0 +1,195 @@
+//! Este módulo contiene los tests de las recomendaciones y la calificación de Steam de
+//! cada juego.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::calificacion::{CalificacionSteam, ConteoRecomendaciones, OpcionesCalificacion};
+    use crate::estadisticas_serializables::{OpcionesSalida, ResumenRecomendaciones};
+    use crate::limites::{Limite, LimitesRanking};
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::tests_utilidades::conteo;
+    use crate::{preparar_salida_final, procesar_archivo_con_pool};
+
+    const RUTA_DATASET: &str = "dataset_test_calificacion";
+
+    /// 'Test' que verifica los umbrales de la calificación de Steam.
+    #[test]
+    fn test_umbrales_de_calificacion() {
+        let casos = [
+            (conteo(9, 0), None),
+            (conteo(10, 0), Some(CalificacionSteam::Positiva)),
+            (conteo(50, 0), Some(CalificacionSteam::MuyPositiva)),
+            (
+                conteo(475, 25),
+                Some(CalificacionSteam::AbrumadoramentePositiva),
+            ),
+            (conteo(474, 26), Some(CalificacionSteam::MuyPositiva)),
+            (conteo(8, 2), Some(CalificacionSteam::Positiva)),
+            (conteo(7, 3), Some(CalificacionSteam::MayormentePositiva)),
+            (conteo(4, 6), Some(CalificacionSteam::Variada)),
+            (conteo(2, 8), Some(CalificacionSteam::MayormenteNegativa)),
+            (conteo(1, 9), Some(CalificacionSteam::Negativa)),
+            (conteo(5, 45), Some(CalificacionSteam::MuyNegativa)),
+            (
+                conteo(0, 500),
+                Some(CalificacionSteam::AbrumadoramenteNegativa),
+            ),
+        ];
+
+        for (conteo, esperada) in casos {
+            assert_eq!(conteo.calificacion(), esperada, "{conteo:?}");
+        }
+    }
+
+    /// 'Test' que verifica el conteo de recomendaciones, ignorando las reseñas sin valor.
+    #[test]
+    fn test_conteo_de_recomendaciones() {
+        let mut total = ConteoRecomendaciones::default();
+        assert_eq!(total.porcentaje_positivo(), None);
+
+        for recomendada in [Some(true), Some(true), None, Some(false)] {
+            total.registrar(recomendada);
+        }
+        assert_eq!(total, conteo(2, 1));
+
+        total.sumar(&conteo(1, 0));
+        assert_eq!(total.total(), 4);
+        assert_eq!(total.porcentaje_positivo(), Some(75.0));
+    }
+
+    /// 'Helper' que resume unas recomendaciones en sus positivas, negativas y
+    /// calificación de Steam.
+    fn resumen(recomendaciones: &ResumenRecomendaciones) -> (u64, u64, Option<&'static str>) {
+        (
+            recomendaciones.positive,
+            recomendaciones.negative,
+            recomendaciones.rating,
+        )
+    }
+
+    /// 'Test' que verifica las recomendaciones y la calificación de Steam de cada juego y
+    /// de cada uno de sus idiomas.
+    ///
+    /// El juego "Aclamado" tiene 50 reseñas en "english" (45 positivas) y 4 en "spanish"
+    /// (3 positivas), "Dividido" tiene 6 positivas, 6 negativas y una sin recomendación, y
+    /// "Rechazado" tiene una positiva y 9 negativas.
+    #[test]
+    fn test_recomendaciones_por_juego_e_idioma() {
+        let opciones = OpcionesProcesamiento::default();
+        let conteo = procesar_archivo_con_pool(&[RUTA_DATASET.to_string()], 4, &opciones);
+        let salida = preparar_salida_final(&conteo, &opciones, &OpcionesSalida::default());
+
+        let juegos: Vec<_> = salida
+            .top_games
+            .iter()
+            .map(|juego| {
+                let idiomas: Vec<_> = juego
+                    .languages
+                    .iter()
+                    .map(|idioma| (idioma.language.as_str(), resumen(&idioma.recommendations)))
+                    .collect();
+                (
+                    juego.game.as_str(),
+                    resumen(&juego.recommendations),
+                    idiomas,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            juegos,
+            [
+                (
+                    "Aclamado",
+                    (48, 6, Some("Very Positive")),
+                    vec![
+                        ("english", (45, 5, Some("Very Positive"))),
+                        ("spanish", (3, 1, None)),
+                    ],
+                ),
+                (
+                    "Dividido",
+                    (6, 6, Some("Mixed")),
+                    vec![("english", (6, 6, Some("Mixed")))],
+                ),
+                (
+                    "Rechazado",
+                    (1, 9, Some("Negative")),
+                    vec![("english", (1, 9, Some("Negative")))],
+                ),
+            ]
+        );
+        assert_eq!(
+            salida.top_games[0].recommendations.positive_percentage,
+            Some(88.89)
+        );
+    }
+
+    /// 'Test' que verifica el valor `z` de los niveles de confianza más usados.
+    #[test]
+    fn test_valor_z_de_la_confianza() {
+        for (confianza, esperado) in [(0.8, 1.281_552), (0.95, 1.959_964), (0.99, 2.575_829)] {
+            let opciones = OpcionesCalificacion {
+                confianza,
+                ..OpcionesCalificacion::default()
+            };
+            assert!((opciones.valor_z() - esperado).abs() < 1e-6, "{confianza}");
+        }
+
+        for invalida in [0.0, 1.0, -0.5, 1.5, f64::NAN] {
+            assert!(!OpcionesCalificacion::es_confianza_valida(invalida));
+        }
+    }
+
+    /// 'Test' que verifica que el límite inferior de Wilson penaliza a los juegos con
+    /// pocas reseñas.
+    #[test]
+    fn test_limite_inferior_wilson() {
+        let z = OpcionesCalificacion::default().valor_z();
+        assert_eq!(conteo(0, 0).limite_inferior_wilson(z), None);
+
+        let pocas = conteo(5, 0).limite_inferior_wilson(z).unwrap();
+        let muchas = conteo(950, 50).limite_inferior_wilson(z).unwrap();
+        assert!((pocas - 0.5655).abs() < 1e-4);
+        assert!(pocas < muchas && muchas < 0.95);
+    }
+
+    /// 'Test' que verifica que el ranking por calificación respeta el mínimo de reseñas
+    /// y está ordenado por el límite inferior de Wilson.
+    #[test]
+    fn test_ranking_por_calificacion() {
+        let procesamiento = OpcionesProcesamiento {
+            limites: LimitesRanking {
+                top_calificados: Limite::Todos,
+                ..LimitesRanking::default()
+            },
+            ..OpcionesProcesamiento::default()
+        };
+        let conteo = procesar_archivo_con_pool(&["dataset_test".to_string()], 4, &procesamiento);
+
+        for minimo_reseñas in [1, 100, u64::MAX] {
+            let opciones = OpcionesSalida {
+                calificacion: OpcionesCalificacion {
+                    minimo_reseñas,
+                    ..OpcionesCalificacion::default()
+                },
+                ..OpcionesSalida::default()
+            };
+
+            let salida = preparar_salida_final(&conteo, &procesamiento, &opciones);
+            let juegos_con_minimo = conteo
+                .juegos
+                .values()
+                .filter(|info| info.recomendaciones.total() >= minimo_reseñas)
+                .count();
+            assert_eq!(salida.top_rated_games.len(), juegos_con_minimo);
+            assert!(
+                salida
+                    .top_rated_games
+                    .windows(2)
+                    .all(|par| par[0].wilson_lower_bound >= par[1].wilson_lower_bound)
+            );
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Este módulo contiene los tests del desglose de las reseñas de cada juego por canal de
+//! compra y de la detección de los juegos con sesgo de copias gratuitas.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::calificacion::OpcionesCalificacion;
+    use crate::canales_compra::{CanalCompra, CanalesCompra, ConteoCanal, evaluar_sesgos};
+    use crate::estadisticas_serializables::{OpcionesSalida, PurchaseChannels};
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::tests_utilidades::conteo;
+    use crate::{preparar_salida_final, procesar_archivo_con_pool};
+
+    const RUTA_DATASET: &str = "dataset_test_canales_compra";
+    const CALIFICACION: OpcionesCalificacion = OpcionesCalificacion {
+        confianza: 0.95,
+        minimo_reseñas: 10,
+    };
+
+    /// 'Helper' que devuelve la sección de canales de compra de
+    /// `dataset_test_canales_compra`, comparando los juegos con, al menos, 10 reseñas por
+    /// grupo.
+    fn canales() -> PurchaseChannels {
+        let opciones = OpcionesProcesamiento::default();
+        let salida = OpcionesSalida {
+            calificacion: CALIFICACION,
+            ..OpcionesSalida::default()
+        };
+
+        let conteo = procesar_archivo_con_pool(&[RUTA_DATASET.to_string()], 2, &opciones);
+        preparar_salida_final(&conteo, &opciones, &salida).purchase_channels
+    }
+
+    /// 'Helper' que crea las reseñas por canal de un juego con las recomendaciones de sus
+    /// copias gratuitas y de sus compras en Steam.
+    fn canales_juego(gratis: (u64, u64), steam: (u64, u64)) -> CanalesCompra {
+        let canal = |(positivas, negativas)| ConteoCanal {
+            cantidad: positivas + negativas,
+            recomendaciones: conteo(positivas, negativas),
+        };
+
+        CanalesCompra {
+            steam: canal(steam),
+            gratis: canal(gratis),
+            ..CanalesCompra::default()
+        }
+    }
+
+    /// 'Helper' que verifica que dos valores p coinciden, con un error relativo menor a
+    /// una parte en mil millones.
+    fn assert_valor_p(obtenido: f64, esperado: f64) {
+        assert!(
+            (obtenido - esperado).abs() <= esperado * 1e-9,
+            "{obtenido} != {esperado}"
+        );
+    }
+
+    /// 'Test' que verifica el canal de cada reseña según `steam_purchase` y
+    /// `received_for_free`.
+    #[test]
+    fn test_canal_de_la_reseña() {
+        let casos = [
+            (Some(true), Some(false), Some(CanalCompra::Steam)),
+            (Some(false), Some(false), Some(CanalCompra::Clave)),
+            (Some(false), None, Some(CanalCompra::Clave)),
+            (Some(true), Some(true), Some(CanalCompra::Gratis)),
+            (None, Some(true), Some(CanalCompra::Gratis)),
+            (None, Some(false), None),
+            (None, None, None),
+        ];
+
+        for (compra_en_steam, recibido_gratis, esperado) in casos {
+            assert_eq!(
+                CanalCompra::desde_reseña(compra_en_steam, recibido_gratis),
+                esperado
+            );
+        }
+    }
+
+    /// 'Test' que verifica el valor p de la prueba exacta de Fisher (unilateral), contra
+    /// los valores calculados con fracciones exactas.
+    #[test]
+    fn test_valor_p_mas_positivas() {
+        let casos = [
+            (conteo(12, 0), conteo(7, 7), 0.005_217_391_304_347_826_5),
+            (conteo(8, 2), conteo(8, 2), 0.708_978_328_173_374_6),
+            (conteo(10, 0), conteo(6, 4), 0.043_343_653_250_773_995),
+            (conteo(600, 400), conteo(500, 500), 4.226_859_837_242_821e-6),
+            (conteo(5, 0), conteo(7, 0), 1.0),
+            (conteo(0, 10), conteo(10, 0), 1.0),
+        ];
+
+        for (gratis, compradas, esperado) in casos {
+            assert_valor_p(gratis.valor_p_mas_positivas(&compradas), esperado);
+        }
+    }
+
+    /// 'Test' que verifica el ajuste de los valores p por la cantidad de juegos comparados:
+    /// un juego con un valor p menor a 0.05 deja de ser significativo al compararse junto
+    /// a otros, los valores p iguales reciben el mismo ajuste sin importar el orden, y los
+    /// juegos sin suficientes reseñas no se comparan.
+    #[test]
+    fn test_ajuste_por_comparaciones_multiples() {
+        let juegos = [
+            ("A", canales_juego((10, 0), (6, 4))),
+            ("B", canales_juego((12, 0), (6, 6))),
+            ("C", canales_juego((8, 2), (8, 2))),
+            ("D", canales_juego((3, 0), (6, 6))),
+        ];
+
+        let sesgos = evaluar_sesgos(
+            juegos.iter().map(|(juego, canales)| (*juego, canales)),
+            &CALIFICACION,
+        );
+        let nombres: Vec<_> = sesgos.iter().map(|(juego, _)| *juego).collect();
+        assert_eq!(nombres, ["A", "B", "C"]);
+
+        let (a, b, c) = (&sesgos[0].1, &sesgos[1].1, &sesgos[2].1);
+        assert_valor_p(a.valor_p, 0.043_343_653_250_773_995);
+        assert_valor_p(a.valor_p_ajustado, 0.043_343_653_250_773_995 * 3.0 / 2.0);
+        assert!(!a.significativo);
+        assert_valor_p(b.valor_p_ajustado, 0.006_864_988_558_352_402 * 3.0);
+        assert!(b.significativo);
+        assert_valor_p(c.valor_p_ajustado, 0.708_978_328_173_374_6);
+        assert!(!c.significativo);
+
+        let empatados = [
+            ("B", canales_juego((12, 0), (6, 6))),
+            ("E", canales_juego((12, 0), (6, 6))),
+            ("A", canales_juego((10, 0), (6, 4))),
+        ];
+        for orden in [[0, 1, 2], [2, 1, 0]] {
+            let sesgos = evaluar_sesgos(
+                orden.map(|i| (empatados[i].0, &empatados[i].1)),
+                &CALIFICACION,
+            );
+            for (juego, sesgo) in sesgos {
+                let esperado = match juego {
+                    "A" => 0.043_343_653_250_773_995,
+                    _ => 0.006_864_988_558_352_402 * 3.0 / 2.0,
+                };
+                assert_valor_p(sesgo.valor_p_ajustado, esperado);
+            }
+        }
+    }
+
+    /// 'Test' que verifica las reseñas y recomendaciones de cada canal.
+    ///
+    /// El juego 10 tiene 12 copias gratuitas (todas positivas), 12 compras en Steam (6
+    /// positivas), 2 claves (1 positiva) y una reseña sin canal, que no se cuenta.
+    #[test]
+    fn test_reseñas_por_canal() {
+        let canales = canales();
+        assert_eq!(canales.confidence, 0.95);
+        assert_eq!(canales.min_reviews_per_channel, 10);
+
+        let juego = &canales.games[0];
+        assert_eq!((juego.game.as_str(), juego.app_id), ("Sesgado", Some(10)));
+        assert_eq!(juego.steam_purchase.review_count, 12);
+        assert_eq!(juego.steam_purchase.recommendations.positive, 6);
+        assert_eq!(juego.key_activation.review_count, 2);
+        assert_eq!(
+            juego.key_activation.recommendations.positive_percentage,
+            Some(50.0)
+        );
+        assert_eq!(juego.free_copy.review_count, 12);
+        assert_eq!(
+            juego.free_copy.recommendations.positive_percentage,
+            Some(100.0)
+        );
+    }
+
+    /// 'Test' que verifica qué juegos se marcan con sesgo de copias gratuitas.
+    ///
+    /// Las copias gratuitas del juego 10 se comparan con sus 14 copias compradas (12 en
+    /// Steam y 2 con clave, 7 positivas). El juego 20 tiene el mismo porcentaje de
+    /// positivas en ambos grupos y el juego 30 no tiene suficientes reseñas para
+    /// compararlos.
+    #[test]
+    fn test_sesgo_de_copias_gratuitas() {
+        let canales = canales();
+        assert_eq!(canales.tested_games, 2);
+
+        let sesgo = canales.games[0].free_copy_bias.as_ref().unwrap();
+        assert_eq!(sesgo.positive_percentage_difference, 50.0);
+        assert_valor_p(sesgo.p_value, 0.005_217_391_304_347_826_5);
+        assert_valor_p(sesgo.adjusted_p_value, 0.005_217_391_304_347_826_5 * 2.0);
+        assert!(sesgo.significant);
+
+        let sesgo = canales.games[1].free_copy_bias.as_ref().unwrap();
+        assert_eq!(sesgo.positive_percentage_difference, 0.0);
+        assert_valor_p(sesgo.adjusted_p_value, 0.708_978_328_173_374_6);
+        assert!(!sesgo.significant);
+
+        assert!(canales.games[2].free_copy_bias.is_none());
+
+        let sesgados: Vec<_> = canales
+            .biased_games
+            .iter()
+            .map(|juego| juego.game.as_str())
+            .collect();
+        assert_eq!(sesgados, ["Sesgado"]);
+    }
+
+    /// 'Test' que verifica que el nivel de confianza y el mínimo de reseñas por grupo son
+    /// los de `--confianza` y `--minimo-recomendaciones`.
+    #[test]
+    fn test_opciones_de_calificacion() {
+        let juegos = [
+            ("A", canales_juego((10, 0), (6, 4))),
+            ("B", canales_juego((12, 0), (6, 6))),
+        ];
+        let evaluar = |confianza, minimo_reseñas| {
+            let opciones = OpcionesCalificacion {
+                confianza,
+                minimo_reseñas,
+            };
+            evaluar_sesgos(
+                juegos.iter().map(|(juego, canales)| (*juego, canales)),
+                &opciones,
+            )
+            .into_iter()
+            .map(|(juego, sesgo)| (juego, sesgo.significativo))
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(evaluar(0.95, 10), [("A", true), ("B", true)]);
+        assert_eq!(evaluar(0.98, 10), [("A", false), ("B", true)]);
+        assert_eq!(evaluar(0.95, 11), [("B", true)]);
+        assert_eq!(evaluar(0.95, 50), []);
+    }
+}
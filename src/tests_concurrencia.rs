@@ -4,15 +4,19 @@
 #[cfg(test)]
 mod tests {
     // Imports de funciones/estructuras propias.
+    use crate::estadisticas_serializables::OpcionesSalida;
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::segmentador::TamañoChunk;
     use crate::{preparar_salida_final, procesar_archivo_con_pool};
 
     /// 'Helper' para obtener el resultado final (`SalidaFinal`) dado un número de hilos.
     /// Este helper facilita la ejecución del procesamiento del archivo con el número de hilos especificado
     /// y luego prepara el resultado final para ser utilizado en los tests.
     fn obtener_salida_final(hilos: usize) -> crate::estadisticas_serializables::SalidaFinal {
-        let ruta = "dataset_test".to_string();
-        let conteo = procesar_archivo_con_pool(ruta, hilos);
-        preparar_salida_final(&conteo)
+        let ruta = vec!["dataset_test".to_string()];
+        let opciones = OpcionesProcesamiento::default();
+        let conteo = procesar_archivo_con_pool(&ruta, hilos, &opciones);
+        preparar_salida_final(&conteo, &opciones, &OpcionesSalida::default())
     }
 
     /// 'Test' para verificar los resultados con diferentes números de hilos.
@@ -135,4 +139,33 @@ mod tests {
             }
         }
     }
+
+    /// 'Test' para verificar que el tamaño de los chunks y el límite de chunks en vuelo no
+    /// cambian el resultado.
+    ///
+    /// Se procesa el dataset con chunks de muy pocas filas, con chunks por bytes y con un
+    /// único chunk en vuelo (el productor se bloquea en cada chunk), comparando las
+    /// estadísticas globales contra las obtenidas con las opciones por defecto.
+    #[test]
+    fn test_resultados_con_distintos_tamaños_de_chunk() {
+        let ruta = vec!["dataset_test".to_string()];
+        let base = procesar_archivo_con_pool(&ruta, 4, &OpcionesProcesamiento::default());
+        let opciones_a_probar = vec![
+            OpcionesProcesamiento {
+                tamaño_chunk: TamañoChunk::Filas(7),
+                max_chunks_en_vuelo: Some(1),
+                ..OpcionesProcesamiento::default()
+            },
+            OpcionesProcesamiento {
+                tamaño_chunk: TamañoChunk::Bytes(512),
+                max_chunks_en_vuelo: Some(3),
+                ..OpcionesProcesamiento::default()
+            },
+        ];
+
+        for opciones in &opciones_a_probar {
+            let conteo = procesar_archivo_con_pool(&ruta, 4, opciones);
+            assert_eq!(base, conteo);
+        }
+    }
 }
@@ -0,0 +1,95 @@
+//! Este módulo contiene los tests que verifican el modo deduplicado, que cuenta
+//! una sola vez las reseñas repetidas entre archivos.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::metricas::ValorMetrica;
+    use crate::procesadores::{OpcionesProcesamiento, procesar_csv_con_rayon};
+    use crate::procesar_archivo_con_pool;
+    use crate::segmentador::TamañoChunk;
+
+    const RUTA_DATASET: &str = "dataset_test";
+    const RUTA_DATASET_DUPLICADOS: &str = "dataset_test_duplicados";
+
+    /// 'Test' que verifica que, como los 10 archivos de `dataset_test` son copias, al
+    /// deduplicar se obtienen las mismas estadísticas que procesando sólo el primero.
+    #[test]
+    fn test_copias_entre_archivos() {
+        let opciones = OpcionesProcesamiento {
+            tamaño_chunk: TamañoChunk::Filas(10),
+            deduplicar: true,
+            ..OpcionesProcesamiento::default()
+        };
+
+        let conteo = procesar_archivo_con_pool(&[RUTA_DATASET.to_string()], 4, &opciones);
+        let original = procesar_archivo_con_pool(
+            &[format!("{}/reviews_test_1.csv", RUTA_DATASET)],
+            4,
+            &OpcionesProcesamiento::default(),
+        );
+
+        assert_eq!(conteo.juegos, original.juegos);
+        assert_eq!(conteo.por_idioma, original.por_idioma);
+        assert_eq!(conteo.ingesta.filas_duplicadas(), 9 * 63);
+    }
+
+    /// 'Test' que verifica que se conserva la copia actualizada más recientemente y, ante
+    /// un empate, la primera en aparecer.
+    ///
+    /// La reseña 1 aparece en `reviews_a.csv` (actualizada en 100) y dos veces en
+    /// `reviews_b.csv` (en 200 y en 150); la reseña 3 aparece en ambos archivos con la
+    /// misma fecha.
+    #[test]
+    fn test_se_conserva_la_copia_mas_reciente() {
+        for filas_por_chunk in [1, 100] {
+            let opciones = OpcionesProcesamiento {
+                tamaño_chunk: TamañoChunk::Filas(filas_por_chunk),
+                deduplicar: true,
+                ..OpcionesProcesamiento::default()
+            };
+
+            let conteo =
+                procesar_archivo_con_pool(&[RUTA_DATASET_DUPLICADOS.to_string()], 2, &opciones);
+
+            let archivo_a = &conteo.ingesta.archivos["dataset_test_duplicados/reviews_a.csv"];
+            let archivo_b = &conteo.ingesta.archivos["dataset_test_duplicados/reviews_b.csv"];
+            assert_eq!(
+                (archivo_a.filas_aceptadas, archivo_a.filas_duplicadas),
+                (2, 1)
+            );
+            assert_eq!(
+                (archivo_b.filas_aceptadas, archivo_b.filas_duplicadas),
+                (1, 2)
+            );
+
+            let idioma = &conteo.por_idioma["english"];
+            assert_eq!(idioma.cantidad_total, 3);
+            let reseñas: Vec<_> = idioma
+                .top_reviews
+                .ordenados()
+                .iter()
+                .map(|reseña| (reseña.texto.as_str(), reseña.valor))
+                .collect();
+            assert_eq!(
+                reseñas,
+                vec![
+                    ("Version nueva", ValorMetrica::Entero(9)),
+                    ("Otra reseña", ValorMetrica::Entero(1)),
+                    ("Copia empatada", ValorMetrica::Entero(0))
+                ]
+            );
+        }
+    }
+
+    /// 'Test' que verifica que no se puede deduplicar leyendo de la entrada estándar.
+    #[test]
+    fn test_no_se_deduplica_la_entrada_estandar() {
+        let opciones = OpcionesProcesamiento {
+            deduplicar: true,
+            ..OpcionesProcesamiento::default()
+        };
+
+        assert!(procesar_csv_con_rayon(&["-".to_string()], &opciones).is_err());
+    }
+}
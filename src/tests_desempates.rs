@@ -0,0 +1,114 @@
+//! Este módulo contiene los tests que verifican que los empates en los rankings se
+//! resuelven con un orden total, independiente del orden de procesamiento.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::estadisticas::ReseñaDestacada;
+    use crate::estadisticas_serializables::OpcionesSalida;
+    use crate::linea_de_tiempo::Periodo;
+    use crate::metricas::ValorMetrica;
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::segmentador::TamañoChunk;
+    use crate::{preparar_salida_final, procesar_archivo_con_pool};
+
+    const DATASETS: [&str; 5] = [
+        "dataset",
+        "dataset_test",
+        "dataset_test_acceso_anticipado",
+        "dataset_test_canales_compra",
+        "dataset_test_metricas",
+    ];
+
+    /// 'Helper' que crea una reseña destacada.
+    fn reseña(texto: &str, votos: u64, id: Option<u64>) -> ReseñaDestacada {
+        ReseñaDestacada {
+            texto: texto.to_string(),
+            valor: ValorMetrica::Entero(votos),
+            id,
+        }
+    }
+
+    /// 'Test' que verifica que las reseñas con los mismos votos se ordenan por
+    /// `review_id` (las que no lo tienen, al final) y luego por texto.
+    #[test]
+    fn test_orden_de_reseñas_empatadas() {
+        let mut reseñas = [
+            reseña("Sin id B", 5, None),
+            reseña("Id alto", 5, Some(9)),
+            reseña("Pocos votos", 1, Some(1)),
+            reseña("Sin id A", 5, None),
+            reseña("Id bajo", 5, Some(2)),
+            reseña("Muchos votos", 8, Some(7)),
+        ];
+
+        reseñas.sort();
+        let textos: Vec<_> = reseñas.iter().map(|r| r.texto.as_str()).collect();
+        assert_eq!(
+            textos,
+            vec![
+                "Muchos votos",
+                "Id bajo",
+                "Id alto",
+                "Sin id A",
+                "Sin id B",
+                "Pocos votos"
+            ]
+        );
+    }
+
+    /// 'Test' que verifica que los juegos con la misma cantidad de reseñas y el mismo
+    /// nombre se ordenan por `app_id`.
+    ///
+    /// En `dataset_test_juegos`, los juegos 20 y 30 tienen una reseña cada uno y se
+    /// llaman igual, por lo que el tercer puesto es siempre para el juego 20.
+    #[test]
+    fn test_orden_de_juegos_empatados() {
+        for filas_por_chunk in [1, 100] {
+            let opciones = OpcionesProcesamiento {
+                tamaño_chunk: TamañoChunk::Filas(filas_por_chunk),
+                ..OpcionesProcesamiento::default()
+            };
+
+            let conteo =
+                procesar_archivo_con_pool(&["dataset_test_juegos".to_string()], 4, &opciones);
+            let salida = preparar_salida_final(&conteo, &opciones, &OpcionesSalida::default());
+            let juegos: Vec<_> = salida.top_games.iter().map(|j| j.app_id).collect();
+            assert_eq!(juegos, vec![Some(10), Some(40), Some(20)]);
+        }
+    }
+
+    /// 'Test' que verifica que el ".json" final, con todas sus secciones (incluida la línea
+    /// de tiempo), es idéntico byte a byte sin importar la cantidad de hilos ni el tamaño
+    /// de los chunks, en cada uno de los datasets de los tests.
+    #[test]
+    fn test_salida_identica_byte_a_byte() {
+        for dataset in DATASETS {
+            let ruta = vec![dataset.to_string()];
+            let mut salidas = Vec::new();
+            for hilos in [1, 4, 8] {
+                for tamaño_chunk in [
+                    TamañoChunk::Filas(1),
+                    TamañoChunk::Filas(7),
+                    TamañoChunk::Bytes(512),
+                ] {
+                    let opciones = OpcionesProcesamiento {
+                        tamaño_chunk,
+                        linea_de_tiempo: Some(Periodo::Semana),
+                        ..OpcionesProcesamiento::default()
+                    };
+
+                    let conteo = procesar_archivo_con_pool(&ruta, hilos, &opciones);
+                    let salida =
+                        preparar_salida_final(&conteo, &opciones, &OpcionesSalida::default());
+                    salidas.push(serde_json::to_string_pretty(&salida).unwrap());
+                }
+            }
+
+            assert!(
+                salidas.iter().all(|salida| *salida == salidas[0]),
+                "{dataset}"
+            );
+        }
+    }
+}
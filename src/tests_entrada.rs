@@ -0,0 +1,159 @@
+//! Este módulo contiene los tests que verifican la búsqueda de los archivos
+//! de entrada a procesar.
+
+#[cfg(test)]
+mod tests {
+    // Imports de crates externas.
+    use std::fs::{File, create_dir_all, remove_dir_all};
+    use std::path::{Path, PathBuf};
+
+    // Imports de funciones/estructuras propias.
+    use crate::entrada::{OpcionesEntrada, RUTA_ENTRADA_ESTANDAR, buscar_archivos};
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::procesar_archivo_con_pool;
+
+    const RUTA_DATASET: &str = "dataset_test";
+    const RUTA_DATASET_COMPRIMIDO: &str = "dataset_test_comprimido";
+
+    /// 'Helper' que devuelve los nombres de los archivos encontrados.
+    fn nombres(archivos: &[PathBuf]) -> Vec<String> {
+        archivos
+            .iter()
+            .map(|archivo| archivo.file_name().unwrap().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// 'Test' que verifica que los archivos de un directorio se devuelven ordenados.
+    #[test]
+    fn test_archivos_de_un_directorio_ordenados() {
+        let archivos = buscar_archivos(
+            &[RUTA_DATASET.to_string()],
+            &OpcionesEntrada::default(),
+            false,
+        )
+        .unwrap();
+
+        let mut esperados = nombres(&archivos);
+        esperados.sort();
+        assert_eq!(archivos.len(), 10);
+        assert_eq!(nombres(&archivos), esperados);
+    }
+
+    /// 'Test' que verifica que se pueden mezclar archivos y directorios, respetando el orden
+    /// de las rutas y sin procesar dos veces el mismo archivo.
+    #[test]
+    fn test_archivos_y_directorios_sin_repetidos() {
+        let rutas = vec![
+            format!("{}/reviews_test_2.csv", RUTA_DATASET),
+            RUTA_DATASET.to_string(),
+        ];
+
+        let archivos = buscar_archivos(&rutas, &OpcionesEntrada::default(), false).unwrap();
+        assert_eq!(archivos.len(), 10);
+        assert_eq!(archivos[0], Path::new(&rutas[0]));
+    }
+
+    /// 'Test' que verifica los patrones de inclusión y exclusión.
+    #[test]
+    fn test_patrones_de_inclusion_y_exclusion() {
+        let opciones = OpcionesEntrada {
+            incluir: vec!["*_1*.csv".to_string()],
+            excluir: vec!["*_10.csv".to_string()],
+            ..OpcionesEntrada::default()
+        };
+
+        let archivos = buscar_archivos(&[RUTA_DATASET.to_string()], &opciones, false).unwrap();
+        assert_eq!(nombres(&archivos), vec!["reviews_test_1.csv"]);
+    }
+
+    /// 'Test' que verifica que los subdirectorios sólo se recorren en modo recursivo, y que
+    /// los patrones se aplican sobre la ruta relativa al directorio de entrada.
+    #[test]
+    fn test_recorrido_recursivo() {
+        let raiz = std::env::temp_dir().join(format!("tests_entrada_{}", std::process::id()));
+        for archivo in [
+            "a.csv",
+            "2021/b.csv",
+            "2021/enero/c.csv",
+            "2022/d.csv",
+            "e.txt",
+        ] {
+            let ruta = raiz.join(archivo);
+            create_dir_all(ruta.parent().unwrap()).unwrap();
+            File::create(ruta).unwrap();
+        }
+
+        let rutas = [raiz.display().to_string()];
+        let plano = buscar_archivos(&rutas, &OpcionesEntrada::default(), false).unwrap();
+        let recursivo = OpcionesEntrada {
+            recursivo: true,
+            ..OpcionesEntrada::default()
+        };
+
+        let todos = buscar_archivos(&rutas, &recursivo, false).unwrap();
+        let filtrados = OpcionesEntrada {
+            incluir: vec!["2021/**".to_string()],
+            ..recursivo
+        };
+
+        let de_2021 = buscar_archivos(&rutas, &filtrados, false).unwrap();
+        remove_dir_all(&raiz).unwrap();
+
+        assert_eq!(nombres(&plano), vec!["a.csv"]);
+        assert_eq!(nombres(&todos), vec!["b.csv", "c.csv", "d.csv", "a.csv"]);
+        assert_eq!(nombres(&de_2021), vec!["b.csv", "c.csv"]);
+    }
+
+    /// 'Test' que verifica que `-` se acepta como entrada (la entrada estándar) aunque no
+    /// exista un archivo con ese nombre.
+    #[test]
+    fn test_entrada_estandar() {
+        let rutas = [RUTA_ENTRADA_ESTANDAR.to_string(), RUTA_DATASET.to_string()];
+        let archivos = buscar_archivos(&rutas, &OpcionesEntrada::default(), false).unwrap();
+        assert_eq!(archivos.len(), 11);
+        assert_eq!(archivos[0], Path::new(RUTA_ENTRADA_ESTANDAR));
+    }
+
+    /// 'Test' que verifica que una ruta inexistente es un error.
+    #[test]
+    fn test_ruta_inexistente() {
+        let rutas = ["no_existe".to_string()];
+        assert!(buscar_archivos(&rutas, &OpcionesEntrada::default(), false).is_err());
+    }
+
+    /// 'Test' que verifica que los `.csv.gz` y `.csv.zst` se descomprimen al vuelo y dan
+    /// las mismas estadísticas que el `.csv` original.
+    #[test]
+    fn test_archivos_comprimidos() {
+        let comprimidos = buscar_archivos(
+            &[RUTA_DATASET_COMPRIMIDO.to_string()],
+            &OpcionesEntrada::default(),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            nombres(&comprimidos),
+            vec!["reviews_test_1.csv.gz", "reviews_test_1.csv.zst"]
+        );
+
+        let original = procesar_archivo_con_pool(
+            &[format!("{}/reviews_test_1.csv", RUTA_DATASET)],
+            2,
+            &OpcionesProcesamiento::default(),
+        );
+
+        for comprimido in &comprimidos {
+            let ruta = comprimido.display().to_string();
+            let conteo = procesar_archivo_con_pool(
+                std::slice::from_ref(&ruta),
+                2,
+                &OpcionesProcesamiento::default(),
+            );
+
+            assert_eq!(conteo.juegos, original.juegos);
+            assert_eq!(conteo.por_idioma, original.por_idioma);
+            assert_eq!(conteo.ingesta.archivos[&ruta].filas_aceptadas, 63);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Este módulo contiene los tests del estimador de autores distintos.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::estadisticas_serializables::OpcionesSalida;
+    use crate::hyperloglog::{HyperLogLog, PRECISION_MAXIMA, PRECISION_MINIMA};
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::{preparar_salida_final, procesar_archivo_con_pool};
+
+    /// 'Helper' que crea un estimador con la precisión y los valores indicados.
+    fn estimador(precision: u8, valores: impl IntoIterator<Item = u64>) -> HyperLogLog {
+        let mut estimador = HyperLogLog::default();
+        for valor in valores {
+            estimador.registrar(precision, valor);
+        }
+        estimador
+    }
+
+    /// 'Test' que verifica que la estimación está a menos de tres errores estándar de la
+    /// cantidad exacta de valores distintos, para todas las precisiones.
+    #[test]
+    fn test_estimacion_con_error_acotado() {
+        // Identificadores consecutivos (como los `steamid`) repetidos varias veces.
+        let distintos = 100_000u64;
+        let valores = (0..3 * distintos).map(|i| 76_561_197_960_265_728 + i % distintos);
+
+        for precision in PRECISION_MINIMA..=PRECISION_MAXIMA {
+            let estimador = estimador(precision, valores.clone());
+            let error = estimador.error_relativo().unwrap();
+            let diferencia = (estimador.estimacion() as f64 - distintos as f64).abs();
+            assert!(diferencia <= 3.0 * error * distintos as f64, "{precision}");
+        }
+    }
+
+    /// 'Test' que verifica que las cantidades chicas de valores distintos se estiman casi
+    /// sin error, y que un estimador vacío no tiene estimación ni error.
+    #[test]
+    fn test_pocos_valores() {
+        let vacio = HyperLogLog::default();
+        assert_eq!(vacio.estimacion(), 0);
+        assert_eq!(vacio.error_relativo(), None);
+
+        for distintos in [1, 2, 10, 50] {
+            let estimador = estimador(12, (0..distintos).chain(0..distintos));
+            assert_eq!(estimador.estimacion(), distintos);
+        }
+    }
+
+    /// 'Test' que verifica que la fusión de estimadores es exacta: da lo mismo que
+    /// registrar todos los valores en un único estimador, sin importar cómo se repartan,
+    /// tanto con pocos registros no nulos (dispersos) como con muchos (densos).
+    #[test]
+    fn test_fusion_de_estimadores() {
+        let valores: Vec<u64> = (0..5000u64).map(|i| (i * 7919) % 3000).collect();
+        let completo = estimador(10, valores.iter().copied());
+
+        for corte in [0, 1, 60, 120, 2500, 4999, 5000] {
+            let (primeros, ultimos) = valores.split_at(corte);
+            let mut destino = estimador(10, ultimos.iter().copied());
+            estimador(10, primeros.iter().copied()).merge_into(&mut destino);
+            assert_eq!(destino, completo);
+
+            let mut destino = estimador(10, primeros.iter().copied());
+            estimador(10, ultimos.iter().copied()).merge_into(&mut destino);
+            assert_eq!(destino, completo);
+        }
+
+        let pocos = &valores[..120];
+        let completo_pocos = estimador(10, pocos.iter().copied());
+        for corte in [0, 1, 60, 119, 120] {
+            let (primeros, ultimos) = pocos.split_at(corte);
+            let mut destino = estimador(10, ultimos.iter().copied());
+            estimador(10, primeros.iter().copied()).merge_into(&mut destino);
+            assert_eq!(destino, completo_pocos);
+            assert_eq!(destino.estimacion(), completo_pocos.estimacion());
+        }
+
+        let mut vacio = HyperLogLog::default();
+        completo.clone().merge_into(&mut vacio);
+        assert_eq!(vacio, completo);
+    }
+
+    /// 'Test' que verifica que fusionar estimadores de distinta precisión falla, en vez de
+    /// combinar sólo los primeros registros.
+    #[test]
+    #[should_panic(expected = "misma precisión")]
+    fn test_fusion_de_precisiones_distintas() {
+        let mut destino = estimador(10, 0..1000);
+        estimador(12, 0..1000).merge_into(&mut destino);
+    }
+
+    /// 'Test' que verifica los autores distintos de cada juego e idioma de `dataset`, en
+    /// el que cada autor escribe una única reseña por juego e idioma.
+    #[test]
+    fn test_autores_distintos_por_juego_e_idioma() {
+        let opciones = OpcionesProcesamiento::default();
+        let conteo = procesar_archivo_con_pool(&["dataset".to_string()], 4, &opciones);
+        let salida = preparar_salida_final(&conteo, &opciones, &OpcionesSalida::default());
+        let juegos = salida
+            .top_games
+            .iter()
+            .map(|j| (j.review_count, j.unique_reviewers));
+        let idiomas = salida
+            .top_languages
+            .iter()
+            .map(|i| (i.review_count, i.unique_reviewers));
+
+        for (reseñas, autores) in juegos.chain(idiomas) {
+            assert_eq!(u64::from(reseñas), autores);
+        }
+    }
+}
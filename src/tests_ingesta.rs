@@ -0,0 +1,282 @@
+//! Este módulo contiene los tests que verifican el reporte de ingesta de
+//! archivos con filas malformadas.
+
+#[cfg(test)]
+mod tests {
+    // Imports de crates externas.
+    use rayon::ThreadPoolBuilder;
+
+    // Imports de funciones/estructuras propias.
+    use crate::estadisticas::ClaveJuego;
+    use crate::estadisticas_serializables::OpcionesSalida;
+    use crate::metricas::ValorMetrica;
+    use crate::procesadores::{
+        OpcionesProcesamiento, PoliticaVotosDesbordados, procesar_csv_con_rayon,
+    };
+    use crate::reporte_ingesta::{ErrorIngesta, MotivoRechazo};
+    use crate::segmentador::TamañoChunk;
+    use crate::{preparar_salida_final, procesar_archivo_con_pool};
+
+    const RUTA_DATASET: &str = "dataset_test_ingesta";
+    const ARCHIVO_MALFORMADO: &str = "dataset_test_ingesta/reviews_malformadas.csv";
+
+    /// 'Test' que verifica que cada fila rechazada se cuenta con su motivo y su línea,
+    /// sin importar en cuántos chunks se divida el archivo.
+    ///
+    /// El archivo tiene 7 filas: 2 válidas, una con votos no numéricos (línea 3), una con
+    /// votos que no entran en un `u32` (línea 4), dos con columnas faltantes (líneas 5 y 7,
+    /// la primera con un campo entre comillas que ocupa dos líneas) y una con UTF-8
+    /// inválido (línea 8).
+    #[test]
+    fn test_reporte_de_filas_rechazadas() {
+        for filas_por_chunk in [1, 2, 100] {
+            let opciones = OpcionesProcesamiento {
+                tamaño_chunk: TamañoChunk::Filas(filas_por_chunk),
+                ..OpcionesProcesamiento::default()
+            };
+
+            let conteo = procesar_archivo_con_pool(&[RUTA_DATASET.to_string()], 2, &opciones);
+            let reporte = &conteo.ingesta.archivos[ARCHIVO_MALFORMADO];
+            assert_eq!(reporte.filas_leidas, 7);
+            assert_eq!(reporte.filas_aceptadas, 2);
+
+            let esperados = [
+                (MotivoRechazo::Utf8Invalido, vec![8]),
+                (MotivoRechazo::ColumnaFaltante, vec![5, 7]),
+                (MotivoRechazo::VotosDesbordados, vec![4]),
+                (MotivoRechazo::VotosNoNumericos, vec![3]),
+            ];
+
+            assert_eq!(reporte.rechazos.len(), esperados.len());
+            for (motivo, lineas) in esperados {
+                let rechazos = &reporte.rechazos[&motivo];
+                assert_eq!(rechazos.cantidad, lineas.len() as u64);
+                assert_eq!(rechazos.primeras_lineas, lineas);
+            }
+        }
+    }
+
+    /// 'Test' que verifica que, en el modo estricto, la primera fila malformada aborta el
+    /// procesamiento con su archivo, línea y columna.
+    #[test]
+    fn test_modo_estricto_aborta_en_la_primera_fila_malformada() {
+        let opciones = OpcionesProcesamiento {
+            estricto: true,
+            ..OpcionesProcesamiento::default()
+        };
+
+        let error = match procesar_csv_con_rayon(&[RUTA_DATASET.to_string()], &opciones) {
+            Ok(_) => panic!("El modo estricto debería rechazar el archivo malformado"),
+            Err(e) => e,
+        };
+
+        match error.downcast_ref::<ErrorIngesta>() {
+            Some(ErrorIngesta::RegistroInvalido {
+                archivo,
+                linea,
+                rechazo,
+            }) => {
+                assert_eq!(archivo, ARCHIVO_MALFORMADO);
+                assert_eq!(*linea, 3);
+                assert_eq!(rechazo.columna, Some(10));
+                assert_eq!(rechazo.motivo, MotivoRechazo::VotosNoNumericos);
+            }
+            _ => panic!("Error inesperado: {}", error),
+        }
+    }
+
+    /// 'Test' que verifica que, en el modo estricto, el error informado es el de la fila
+    /// malformada más temprana de la entrada, aunque los chunks de un archivo posterior se
+    /// procesen antes.
+    ///
+    /// El primer archivo tiene 200 filas válidas y una malformada en la línea 202, y el
+    /// segundo tiene una fila malformada en la línea 2.
+    #[test]
+    fn test_modo_estricto_informa_la_fila_malformada_mas_temprana() {
+        let directorio = std::env::temp_dir().join(format!("tests_ingesta_{}", std::process::id()));
+        std::fs::create_dir_all(&directorio).unwrap();
+        let encabezado = ",app_id,app_name,review_id,language,review,timestamp_created,timestamp_updated,recommended,votes_helpful";
+        let fila = |votos: &str| format!("0,1,Juego,2,english,Texto,1,1,True,{}", votos);
+        let validas: Vec<_> = (0..200).map(|_| fila("1")).collect();
+        let archivos = [
+            (
+                "a.csv",
+                format!("{}\n{}\n{}\n", encabezado, validas.join("\n"), fila("x")),
+            ),
+            ("b.csv", format!("{}\n{}\n", encabezado, fila("x"))),
+        ];
+        let rutas: Vec<_> = archivos
+            .iter()
+            .map(|(nombre, contenido)| {
+                let ruta = directorio.join(nombre);
+                std::fs::write(&ruta, contenido).unwrap();
+                ruta.display().to_string()
+            })
+            .collect();
+
+        let opciones = OpcionesProcesamiento {
+            estricto: true,
+            tamaño_chunk: TamañoChunk::Filas(1),
+            ..OpcionesProcesamiento::default()
+        };
+        let pool = ThreadPoolBuilder::new().num_threads(8).build().unwrap();
+        for _ in 0..20 {
+            let informado = pool.install(|| {
+                let error = procesar_csv_con_rayon(&rutas, &opciones).unwrap_err();
+                match error.downcast_ref::<ErrorIngesta>() {
+                    Some(ErrorIngesta::RegistroInvalido { archivo, linea, .. }) => {
+                        (archivo.clone(), *linea)
+                    }
+                    _ => panic!("Error inesperado: {}", error),
+                }
+            });
+            assert_eq!(informado, (rutas[0].clone(), 202));
+        }
+
+        std::fs::remove_dir_all(&directorio).unwrap();
+    }
+
+    /// 'Helper' que escribe un archivo con filas cuyas columnas opcionales tienen valores
+    /// inválidos en un directorio temporal propio del 'test', y devuelve su ruta.
+    ///
+    /// Las filas de las líneas 3 ("app_id" no numérico), 4 ("recommended" que no es un
+    /// booleano) y 6 ("timestamp_updated" que no es una marca temporal, y "recommended" que
+    /// no es un booleano) tienen valores inválidos.
+    fn escribir_archivo_con_campos_invalidos(nombre: &str) -> String {
+        let directorio = std::env::temp_dir().join(format!("{}_{}", nombre, std::process::id()));
+        std::fs::create_dir_all(&directorio).unwrap();
+        let contenido = [
+            ",app_id,app_name,review_id,language,review,timestamp_created,timestamp_updated,recommended,votes_helpful",
+            "0,1,Juego,1,english,Texto,1,1,True,1",
+            "1,abc,Juego,2,english,Texto,1,1,True,1",
+            "2,1,Juego,3,english,Texto,1,1,maybe,1",
+            "3,1,Juego,4,english,Texto,1,1,False,1",
+            "4,1,Juego,5,english,Texto,1,ayer,quizas,1",
+        ];
+        let ruta = directorio.join("campos_invalidos.csv");
+        std::fs::write(&ruta, contenido.join("\n") + "\n").unwrap();
+        ruta.display().to_string()
+    }
+
+    /// 'Test' que verifica que las filas con valores inválidos en columnas opcionales se
+    /// aceptan sin esos valores y se informan en el reporte de ingesta.
+    #[test]
+    fn test_reporte_de_campos_invalidos() {
+        let ruta = escribir_archivo_con_campos_invalidos("tests_ingesta_campos_invalidos");
+
+        for filas_por_chunk in [1, 2, 100] {
+            let opciones = OpcionesProcesamiento {
+                tamaño_chunk: TamañoChunk::Filas(filas_por_chunk),
+                ..OpcionesProcesamiento::default()
+            };
+
+            let conteo = procesar_archivo_con_pool(std::slice::from_ref(&ruta), 2, &opciones);
+            let reporte = &conteo.ingesta.archivos[&ruta];
+            assert_eq!(reporte.filas_leidas, 5);
+            assert_eq!(reporte.filas_aceptadas, 5);
+            assert!(reporte.rechazos.is_empty());
+            assert_eq!(reporte.campos_invalidos.cantidad, 3);
+            assert_eq!(reporte.campos_invalidos.primeras_lineas, vec![3, 4, 6]);
+
+            // La reseña con "app_id" inválido se agrupa sin id, y el resto con el id 1.
+            assert_eq!(
+                conteo.juegos[&ClaveJuego::SinId("Juego".into())].cantidad_total,
+                1
+            );
+            assert_eq!(conteo.juegos[&ClaveJuego::Id(1)].cantidad_total, 4);
+        }
+
+        std::fs::remove_dir_all(std::path::Path::new(&ruta).parent().unwrap()).unwrap();
+    }
+
+    /// 'Test' que verifica que, en el modo estricto, el primer valor inválido de una columna
+    /// opcional aborta el procesamiento con su línea y su columna, también cuando la columna
+    /// no es numérica.
+    #[test]
+    fn test_modo_estricto_aborta_en_el_primer_campo_invalido() {
+        let ruta = escribir_archivo_con_campos_invalidos("tests_ingesta_estricto_campos_invalidos");
+        let opciones = OpcionesProcesamiento {
+            estricto: true,
+            tamaño_chunk: TamañoChunk::Filas(1),
+            ..OpcionesProcesamiento::default()
+        };
+
+        let error = procesar_csv_con_rayon(std::slice::from_ref(&ruta), &opciones).unwrap_err();
+        match error.downcast_ref::<ErrorIngesta>() {
+            Some(ErrorIngesta::RegistroInvalido { linea, rechazo, .. }) => {
+                assert_eq!(*linea, 3);
+                assert_eq!(rechazo.columna, Some(2));
+                assert_eq!(rechazo.motivo, MotivoRechazo::CampoInvalido);
+            }
+            _ => panic!("Error inesperado: {}", error),
+        }
+
+        // Sin la fila de la línea 3, el primer valor inválido es el booleano de la línea 4.
+        let contenido = std::fs::read_to_string(&ruta).unwrap();
+        let sin_id_invalido: Vec<_> = contenido.lines().filter(|l| !l.contains("abc")).collect();
+        std::fs::write(&ruta, sin_id_invalido.join("\n") + "\n").unwrap();
+
+        let error = procesar_csv_con_rayon(std::slice::from_ref(&ruta), &opciones).unwrap_err();
+        match error.downcast_ref::<ErrorIngesta>() {
+            Some(ErrorIngesta::RegistroInvalido { linea, rechazo, .. }) => {
+                assert_eq!(*linea, 3);
+                assert_eq!(rechazo.columna, Some(9));
+                assert_eq!(rechazo.motivo, MotivoRechazo::CampoInvalido);
+            }
+            _ => panic!("Error inesperado: {}", error),
+        }
+
+        std::fs::remove_dir_all(std::path::Path::new(&ruta).parent().unwrap()).unwrap();
+    }
+
+    /// 'Test' que verifica cada política de votos desbordados sobre la fila de la línea 4,
+    /// una reseña en "schinese" con 99999999999 votos (que entran en un `u64`), y que la
+    /// política se indica en el ".json".
+    #[test]
+    fn test_politicas_de_votos_desbordados() {
+        let casos = [
+            (PoliticaVotosDesbordados::Descartar, 2, 1, None),
+            (PoliticaVotosDesbordados::IgnorarVotos, 3, 2, None),
+            (
+                PoliticaVotosDesbordados::Saturar,
+                3,
+                2,
+                Some(u32::MAX as u64),
+            ),
+            (PoliticaVotosDesbordados::Ampliar, 3, 2, Some(99999999999)),
+        ];
+
+        for (politica, aceptadas, reseñas_idioma, votos_maximos) in casos {
+            let opciones = OpcionesProcesamiento {
+                votos_desbordados: politica,
+                ..OpcionesProcesamiento::default()
+            };
+
+            let conteo = procesar_archivo_con_pool(&[RUTA_DATASET.to_string()], 2, &opciones);
+            let reporte = &conteo.ingesta.archivos[ARCHIVO_MALFORMADO];
+            assert_eq!(reporte.filas_aceptadas, aceptadas);
+            assert_eq!(
+                reporte
+                    .rechazos
+                    .contains_key(&MotivoRechazo::VotosDesbordados),
+                politica == PoliticaVotosDesbordados::Descartar
+            );
+
+            let idioma = &conteo.por_idioma["schinese"];
+            assert_eq!(idioma.cantidad_total, reseñas_idioma);
+            let maximo = idioma
+                .top_reviews
+                .ordenados()
+                .iter()
+                .map(|reseña| reseña.valor)
+                .max();
+            assert_eq!(
+                maximo.filter(|votos| *votos > ValorMetrica::Entero(0)),
+                votos_maximos.map(ValorMetrica::Entero)
+            );
+
+            let salida = preparar_salida_final(&conteo, &opciones, &OpcionesSalida::default());
+            assert_eq!(salida.vote_overflow_policy, politica.nombre());
+        }
+    }
+}
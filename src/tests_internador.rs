@@ -0,0 +1,31 @@
+//! Este módulo contiene los tests que verifican el internador de los nombres de
+//! juegos e idiomas.
+
+#[cfg(test)]
+mod tests {
+    // Imports de crates externas.
+    use std::sync::Arc;
+
+    // Imports de funciones/estructuras propias.
+    use crate::internador::Internador;
+
+    /// 'Test' que verifica que una misma cadena se interna una sola vez, aunque se pida
+    /// desde distintas caches locales.
+    #[test]
+    fn test_misma_cadena_compartida() {
+        let internador = Internador::default();
+        let mut cache_a = internador.cache();
+        let mut cache_b = internador.cache();
+
+        let english_a = cache_a.internar("english");
+        let english_b = cache_b.internar("english");
+        let english_c = cache_a.internar("english");
+        let spanish = cache_b.internar("spanish");
+
+        assert!(Arc::ptr_eq(&english_a, &english_b));
+        assert!(Arc::ptr_eq(&english_a, &english_c));
+        assert!(Arc::ptr_eq(&spanish, &internador.internar("spanish")));
+        assert_eq!(&*english_a, "english");
+        assert!(!Arc::ptr_eq(&english_a, &spanish));
+    }
+}
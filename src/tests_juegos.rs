@@ -0,0 +1,47 @@
+//! Este módulo contiene los tests que verifican la agrupación de las reseñas
+//! por juego (`app_id`) y la elección de su nombre.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::estadisticas::ClaveJuego;
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::procesar_archivo_con_pool;
+    use crate::segmentador::TamañoChunk;
+
+    const RUTA_DATASET: &str = "dataset_test_juegos";
+
+    /// 'Test' que verifica que un juego renombrado se agrupa en una sola entrada, y que
+    /// dos juegos distintos con el mismo nombre quedan separados.
+    ///
+    /// El juego 10 aparece una vez como "Viejo Nombre" y dos como "Nuevo Nombre"; los
+    /// juegos 20 y 30 se llaman igual; el juego 40 aparece una vez con cada nombre, por
+    /// lo que se muestra con el más reciente.
+    #[test]
+    fn test_juegos_agrupados_por_app_id() {
+        for filas_por_chunk in [1, 100] {
+            let opciones = OpcionesProcesamiento {
+                tamaño_chunk: TamañoChunk::Filas(filas_por_chunk),
+                ..OpcionesProcesamiento::default()
+            };
+
+            let conteo = procesar_archivo_con_pool(&[RUTA_DATASET.to_string()], 2, &opciones);
+            assert_eq!(conteo.juegos.len(), 4);
+
+            let renombrado = &conteo.juegos[&ClaveJuego::Id(10)];
+            assert_eq!(renombrado.cantidad_total, 3);
+            assert_eq!(renombrado.nombre_principal(), "Nuevo Nombre");
+            assert_eq!(renombrado.alias(), vec!["Nuevo Nombre", "Viejo Nombre"]);
+
+            for id in [20, 30] {
+                let juego = &conteo.juegos[&ClaveJuego::Id(id)];
+                assert_eq!(juego.cantidad_total, 1);
+                assert_eq!(juego.nombre_principal(), "Nombre Compartido");
+            }
+
+            let empatado = &conteo.juegos[&ClaveJuego::Id(40)];
+            assert_eq!(empatado.nombre_principal(), "Empate Nuevo");
+            assert_eq!(empatado.alias(), vec!["Empate Nuevo", "Empate Viejo"]);
+        }
+    }
+}
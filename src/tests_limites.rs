@@ -0,0 +1,88 @@
+//! Este módulo contiene los tests que verifican los tamaños configurables de los
+//! rankings del ".json" final.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::estadisticas_serializables::OpcionesSalida;
+    use crate::limites::{Limite, LimitesIndicados, LimitesRanking};
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::{preparar_salida_final, procesar_archivo_con_pool};
+
+    const RUTA_DATASET: &str = "dataset_test";
+
+    /// 'Test' que verifica la interpretación de los límites escritos como texto.
+    #[test]
+    fn test_limite_desde_texto() {
+        assert_eq!(Limite::desde_texto("25").unwrap(), Limite::Cantidad(25));
+        assert_eq!(Limite::desde_texto("all").unwrap(), Limite::Todos);
+        for invalido in ["0", "-3", "todos", ""] {
+            assert!(Limite::desde_texto(invalido).is_err());
+        }
+    }
+
+    /// 'Test' que verifica la lectura del archivo de configuración: los límites que no
+    /// se indican conservan su valor, y los campos desconocidos o inválidos son un error.
+    #[test]
+    fn test_archivo_de_configuracion() {
+        let ruta = std::env::temp_dir().join(format!("tests_limites_{}.json", std::process::id()));
+        let leer = |contenido: &str| {
+            std::fs::write(&ruta, contenido).unwrap();
+            LimitesIndicados::desde_archivo(ruta.to_str().unwrap())
+        };
+
+        let indicados = leer(r#"{ "top_juegos": 25, "top_idiomas": "all" }"#).unwrap();
+        let mut limites = LimitesRanking::default();
+        indicados.aplicar(&mut limites);
+        assert_eq!(
+            limites,
+            LimitesRanking {
+                top_juegos: Limite::Cantidad(25),
+                top_idiomas: Limite::Todos,
+                ..LimitesRanking::default()
+            }
+        );
+
+        for invalido in [
+            r#"{ "top_juegos": 0 }"#,
+            r#"{ "top_juegos": "muchos" }"#,
+            r#"{ "top_jeugos": 10 }"#,
+        ] {
+            assert!(leer(invalido).is_err());
+        }
+
+        std::fs::remove_file(&ruta).unwrap();
+    }
+
+    /// 'Test' que verifica que los rankings respetan los límites, incluyendo `all`.
+    #[test]
+    fn test_rankings_con_limites() {
+        let limites = LimitesRanking {
+            top_juegos: Limite::Todos,
+            idiomas_por_juego: Limite::Cantidad(1),
+            top_idiomas: Limite::Cantidad(2),
+            reviews_por_idioma: Limite::Todos,
+            top_calificados: Limite::Todos,
+        };
+
+        let procesamiento = OpcionesProcesamiento {
+            limites,
+            ..OpcionesProcesamiento::default()
+        };
+
+        let conteo = procesar_archivo_con_pool(&[RUTA_DATASET.to_string()], 4, &procesamiento);
+        let salida = preparar_salida_final(&conteo, &procesamiento, &OpcionesSalida::default());
+
+        assert_eq!(salida.top_games.len(), conteo.juegos.len());
+        assert!(
+            salida
+                .top_games
+                .iter()
+                .all(|juego| juego.languages.len() == 1)
+        );
+        assert_eq!(salida.top_languages.len(), 2);
+        for idioma in &salida.top_languages {
+            assert_eq!(idioma.top_reviews.len(), idioma.review_count as usize);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Este módulo contiene los tests de la línea de tiempo de las reseñas.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::estadisticas_serializables::OpcionesSalida;
+    use crate::linea_de_tiempo::{LineaDeTiempo, Periodo};
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::{preparar_salida_final, procesar_archivo_con_pool};
+
+    // 2021-01-23 (sábado) a las 06:00:29 UTC.
+    const SABADO: i64 = 1_611_381_629;
+    // 2020-02-29 a las 12:00 UTC.
+    const BISIESTO: i64 = 1_582_977_600;
+    // 1969-12-31 a las 23:59:59 UTC.
+    const ANTES_DE_1970: i64 = -1;
+
+    /// 'Helper' que devuelve la etiqueta del período que contiene a `marca`.
+    fn etiqueta(periodo: Periodo, marca: i64) -> String {
+        periodo.etiqueta(periodo.inicio(marca))
+    }
+
+    /// 'Test' que verifica el inicio de los días, las semanas (de lunes a domingo) y los
+    /// meses, incluyendo años bisiestos y fechas anteriores a 1970.
+    #[test]
+    fn test_inicio_de_los_periodos() {
+        assert_eq!(etiqueta(Periodo::Dia, SABADO), "2021-01-23");
+        assert_eq!(etiqueta(Periodo::Semana, SABADO), "2021-01-18");
+        assert_eq!(etiqueta(Periodo::Mes, SABADO), "2021-01");
+
+        assert_eq!(etiqueta(Periodo::Dia, BISIESTO), "2020-02-29");
+        assert_eq!(etiqueta(Periodo::Semana, BISIESTO), "2020-02-24");
+        assert_eq!(etiqueta(Periodo::Mes, BISIESTO), "2020-02");
+
+        assert_eq!(etiqueta(Periodo::Dia, ANTES_DE_1970), "1969-12-31");
+        assert_eq!(etiqueta(Periodo::Semana, ANTES_DE_1970), "1969-12-29");
+        assert_eq!(etiqueta(Periodo::Mes, ANTES_DE_1970), "1969-12");
+    }
+
+    /// 'Test' que verifica que la fusión de líneas de tiempo suma los períodos, sin
+    /// importar en qué orden se registran las reseñas.
+    #[test]
+    fn test_fusion_de_lineas_de_tiempo() {
+        let semana = Periodo::Semana.inicio(SABADO);
+        let otra_semana = Periodo::Semana.inicio(BISIESTO);
+
+        let mut primera = LineaDeTiempo::default();
+        primera.registrar(semana, Some(true));
+        primera.registrar(otra_semana, None);
+        let mut segunda = LineaDeTiempo::default();
+        segunda.registrar(semana, Some(false));
+
+        let mut todas = LineaDeTiempo::default();
+        todas.registrar(otra_semana, None);
+        todas.registrar(semana, Some(false));
+        todas.registrar(semana, Some(true));
+
+        primera.merge_into(&mut segunda);
+        assert_eq!(segunda, todas);
+
+        let periodos: Vec<_> = todas
+            .periodos()
+            .map(|(inicio, conteo)| (inicio, conteo.cantidad, conteo.recomendaciones.total()))
+            .collect();
+        assert_eq!(periodos, vec![(otra_semana, 1, 0), (semana, 2, 2)]);
+    }
+
+    /// 'Helper' que devuelve el ".json" de la línea de tiempo de `dataset_test`, con los
+    /// períodos indicados.
+    fn linea_de_tiempo_json(periodo: Option<Periodo>) -> String {
+        let opciones = OpcionesProcesamiento {
+            linea_de_tiempo: periodo,
+            ..OpcionesProcesamiento::default()
+        };
+
+        let conteo = procesar_archivo_con_pool(&["dataset_test".to_string()], 4, &opciones);
+        let salida = preparar_salida_final(&conteo, &opciones, &OpcionesSalida::default());
+        for (juego, linea) in salida
+            .top_games
+            .iter()
+            .zip(salida.timeline.iter().flat_map(|t| &t.games))
+        {
+            let total: u64 = linea.periods.iter().map(|p| p.review_count).sum();
+            assert_eq!(juego.game, linea.game);
+            assert_eq!(total, u64::from(juego.review_count));
+        }
+
+        serde_json::to_string(&salida.timeline).unwrap()
+    }
+
+    /// 'Test' que verifica que la línea de tiempo sólo se incluye si se pide y suma las
+    /// reseñas de cada juego.
+    #[test]
+    fn test_linea_de_tiempo_por_juego_e_idioma() {
+        assert_eq!(linea_de_tiempo_json(None), "null");
+
+        for periodo in [Periodo::Dia, Periodo::Semana, Periodo::Mes] {
+            assert!(linea_de_tiempo_json(Some(periodo)).contains(periodo.nombre()));
+        }
+    }
+}
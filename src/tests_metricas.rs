@@ -0,0 +1,145 @@
+//! Este módulo contiene los tests de las métricas con las que se eligen las mejores
+//! reseñas, y de las reseñas más graciosas y más comentadas.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::estadisticas_serializables::{OpcionesSalida, SalidaFinal};
+    use crate::metricas::{MetricaReseñas, ValorMetrica};
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::segmentador::TamañoChunk;
+    use crate::{preparar_salida_final, procesar_archivo_con_pool};
+
+    const RUTA_DATASET: &str = "dataset_test_metricas";
+
+    /// 'Helper' que procesa `dataset_test_metricas` eligiendo las mejores reseñas con la
+    /// métrica indicada.
+    fn salida(metrica: MetricaReseñas, filas_por_chunk: usize) -> SalidaFinal {
+        let procesamiento = OpcionesProcesamiento {
+            tamaño_chunk: TamañoChunk::Filas(filas_por_chunk),
+            metrica_reviews: metrica,
+            ..OpcionesProcesamiento::default()
+        };
+        let conteo = procesar_archivo_con_pool(&[RUTA_DATASET.to_string()], 2, &procesamiento);
+        preparar_salida_final(&conteo, &procesamiento, &OpcionesSalida::default())
+    }
+
+    /// 'Test' que verifica el orden de las mejores reseñas con cada métrica, tanto en el
+    /// idioma del juego como en el ranking de idiomas, sin importar el tamaño de los chunks.
+    ///
+    /// Las reseñas en "english" tienen (útiles, graciosos, puntaje, comentarios): "Util"
+    /// (9, 0, 0.5, 0), "Graciosa" (1, 7, 0.6, 1), "Comentada" (2, 1, 0.4, 12) y
+    /// "Ponderada" (3, 0, 0.95, 2).
+    #[test]
+    fn test_mejores_reseñas_segun_la_metrica() {
+        let casos = [
+            (
+                MetricaReseñas::VotosUtiles,
+                ["Util", "Ponderada", "Comentada", "Graciosa"],
+                ValorMetrica::Entero(9),
+            ),
+            (
+                MetricaReseñas::VotosGraciosos,
+                ["Graciosa", "Comentada", "Util", "Ponderada"],
+                ValorMetrica::Entero(7),
+            ),
+            (
+                MetricaReseñas::PuntajePonderado,
+                ["Ponderada", "Graciosa", "Util", "Comentada"],
+                ValorMetrica::Decimal(0.95),
+            ),
+            (
+                MetricaReseñas::Comentarios,
+                ["Comentada", "Ponderada", "Graciosa", "Util"],
+                ValorMetrica::Entero(12),
+            ),
+        ];
+
+        for (metrica, orden, valor_maximo) in casos {
+            for filas_por_chunk in [1, 100] {
+                let salida = salida(metrica, filas_por_chunk);
+                assert_eq!(salida.review_metric, metrica.nombre());
+
+                let idioma_juego = &salida.top_games[0].languages[0];
+                assert_eq!(idioma_juego.language, "english");
+                assert_eq!(idioma_juego.top_review, orden[0]);
+                assert_eq!(idioma_juego.top_review_votes, valor_maximo);
+
+                let idioma = &salida.top_languages[0];
+                let textos: Vec<_> = idioma
+                    .top_reviews
+                    .iter()
+                    .map(|r| r.review.as_str())
+                    .collect();
+                assert_eq!(textos, orden);
+                assert_eq!(idioma.top_reviews[0].votes, valor_maximo);
+            }
+        }
+    }
+
+    /// 'Test' que verifica la reseña más graciosa y la más comentada de cada juego e
+    /// idioma, que no dependen de la métrica elegida y no existen si ninguna reseña tiene
+    /// votos graciosos o comentarios.
+    #[test]
+    fn test_reseñas_mas_graciosas_y_mas_comentadas() {
+        for metrica in [MetricaReseñas::VotosUtiles, MetricaReseñas::Comentarios] {
+            let salida = salida(metrica, 1);
+            let juego = &salida.top_games[0];
+            let (english, spanish) = (&juego.languages[0], &juego.languages[1]);
+
+            let graciosa = english.funniest_review.as_ref().unwrap();
+            assert_eq!(
+                (graciosa.review.as_str(), graciosa.funny_votes),
+                ("Graciosa", ValorMetrica::Entero(7))
+            );
+            let comentada = english.most_discussed_review.as_ref().unwrap();
+            assert_eq!(
+                (comentada.review.as_str(), comentada.comments),
+                ("Comentada", ValorMetrica::Entero(12))
+            );
+            assert!(spanish.funniest_review.is_none());
+            assert!(spanish.most_discussed_review.is_none());
+
+            let idioma = &salida.top_languages[0];
+            let graciosa = idioma.funniest_review.as_ref().unwrap();
+            assert_eq!(
+                (graciosa.review.as_str(), graciosa.funny_votes),
+                ("Graciosa", ValorMetrica::Entero(7))
+            );
+            let comentada = idioma.most_discussed_review.as_ref().unwrap();
+            assert_eq!(
+                (comentada.review.as_str(), comentada.comments),
+                ("Comentada", ValorMetrica::Entero(12))
+            );
+            assert!(salida.top_languages[1].funniest_review.is_none());
+        }
+    }
+
+    /// 'Test' que verifica que los valores de las métricas se ordenan como los números que
+    /// representan, incluso los puntajes decimales.
+    #[test]
+    fn test_orden_de_los_valores() {
+        let mut valores = [
+            ValorMetrica::Decimal(0.5),
+            ValorMetrica::Decimal(0.95),
+            ValorMetrica::Decimal(0.0),
+            ValorMetrica::Decimal(0.125),
+        ];
+        valores.sort();
+        assert_eq!(valores, [0.0, 0.125, 0.5, 0.95].map(ValorMetrica::Decimal));
+        assert!(ValorMetrica::Entero(u32::MAX as u64 + 1) > ValorMetrica::Entero(9));
+    }
+
+    /// 'Test' que verifica cómo se escriben en el ".json" la métrica elegida y sus valores.
+    #[test]
+    fn test_metrica_en_el_json() {
+        let json = serde_json::to_string(&salida(MetricaReseñas::PuntajePonderado, 100)).unwrap();
+        assert!(json.contains(r#""review_metric":"weighted_vote_score""#));
+        assert!(json.contains(r#""top_review_votes":0.95"#));
+        assert!(json.contains(r#""funniest_review":{"review":"Graciosa","funny_votes":7}"#));
+
+        let json = serde_json::to_string(&salida(MetricaReseñas::VotosUtiles, 100)).unwrap();
+        assert!(json.contains(r#""review_metric":"votes_helpful""#));
+        assert!(json.contains(r#""top_review_votes":9"#));
+    }
+}
@@ -0,0 +1,121 @@
+//! Este módulo contiene los 'benchmarks' del procesamiento de reseñas. Se ignoran por
+//! defecto; se ejecutan con:
+//!
+//! `cargo test --release -- --ignored --nocapture`
+
+#[cfg(test)]
+mod tests {
+    // Imports de crates externas.
+    use rayon::ThreadPoolBuilder;
+    use std::time::{Duration, Instant};
+
+    // Imports de funciones/estructuras propias.
+    use crate::estadisticas::EstadisticasGlobales;
+    use crate::procesadores::{
+        OpcionesProcesamiento, procesar_csv_con_rayon, procesar_csv_registrando, registrar_reseña,
+    };
+    use crate::tests_utilidades::en_propiedad;
+
+    const RUTA_DATASET: &str = "dataset/steam_short_reviews.csv";
+    const REPETICIONES_DATASET: usize = 5_000;
+    const MEDICIONES: usize = 10;
+
+    /// 'Helper' que escribe un '.csv' temporal repitiendo las filas del dataset, y
+    /// devuelve su ruta.
+    fn dataset_repetido() -> String {
+        let contenido = std::fs::read_to_string(RUTA_DATASET).unwrap();
+        let (encabezado, filas) = contenido.split_once('\n').unwrap();
+        let mut datos = format!("{}\n", encabezado).into_bytes();
+        for _ in 0..REPETICIONES_DATASET {
+            datos.extend_from_slice(filas.trim_end().as_bytes());
+            datos.push(b'\n');
+        }
+
+        let ruta =
+            std::env::temp_dir().join(format!("tests_rendimiento_{}.csv", std::process::id()));
+        std::fs::write(&ruta, datos).unwrap();
+        ruta.display().to_string()
+    }
+
+    /// 'Helper' que procesa los archivos con el mismo 'pipeline' que
+    /// `procesar_csv_con_rayon`, pero copiando los campos de texto de cada reseña antes de
+    /// registrarla (como hacía el parseo a `String`).
+    fn procesar_copiando_textos(
+        rutas: &[String],
+        opciones: &OpcionesProcesamiento,
+    ) -> Result<EstadisticasGlobales, String> {
+        procesar_csv_registrando(rutas, opciones, |stats, cache, reseña, votos, opciones| {
+            registrar_reseña(stats, cache, &en_propiedad(reseña.clone()), votos, opciones)
+        })
+        .map_err(|error| error.to_string())
+    }
+
+    /// 'Helper' que ejecuta `antes` y `despues` varias veces, alternándolos para que ambos
+    /// se midan en las mismas condiciones, y devuelve el último resultado y la menor de las
+    /// duraciones de cada uno.
+    fn medir_alternados<T>(
+        antes: impl Fn() -> T,
+        despues: impl Fn() -> T,
+    ) -> ((T, Duration), (T, Duration)) {
+        let medir = |procesar: &dyn Fn() -> T, mejor: &mut Duration| {
+            let inicio = Instant::now();
+            let resultado = procesar();
+            *mejor = (*mejor).min(inicio.elapsed());
+            resultado
+        };
+
+        let (mut mejor_antes, mut mejor_despues) = (Duration::MAX, Duration::MAX);
+        let mut resultados = None;
+        for _ in 0..MEDICIONES {
+            resultados = Some((
+                medir(&antes, &mut mejor_antes),
+                medir(&despues, &mut mejor_despues),
+            ));
+        }
+
+        let (resultado_antes, resultado_despues) = resultados.unwrap();
+        (
+            (resultado_antes, mejor_antes),
+            (resultado_despues, mejor_despues),
+        )
+    }
+
+    /// 'Benchmark' que compara, de punta a punta y con un único hilo trabajador, el
+    /// procesamiento de `procesar_csv_con_rayon`, que toma prestados los campos de texto
+    /// de cada reseña, contra el mismo procesamiento copiándolos, sobre
+    /// `dataset/steam_short_reviews.csv` repetido. Se informa la mejor de varias
+    /// mediciones alternadas de cada uno.
+    #[test]
+    #[ignore]
+    fn benchmark_parseo_prestado() {
+        let rutas = [dataset_repetido()];
+        let opciones = OpcionesProcesamiento::default();
+        let pool = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+
+        let ((copiando, antes), (prestando, despues)) = medir_alternados(
+            || pool.install(|| procesar_copiando_textos(&rutas, &opciones)),
+            || {
+                pool.install(|| {
+                    procesar_csv_con_rayon(&rutas, &opciones).map_err(|error| error.to_string())
+                })
+            },
+        );
+        let (copiando, prestando) = (copiando.unwrap(), prestando.unwrap());
+        std::fs::remove_file(&rutas[0]).unwrap();
+
+        assert_eq!(copiando.juegos, prestando.juegos);
+        assert_eq!(copiando.por_idioma, prestando.por_idioma);
+        let cantidad: usize = prestando
+            .juegos
+            .values()
+            .map(|info| info.cantidad_total)
+            .sum();
+        let por_segundo = |duracion: Duration| cantidad as f64 / duracion.as_secs_f64();
+        println!(
+            "Copiando los textos: {:.0} reseñas/s. Con campos prestados: {:.0} reseñas/s ({:.2}x).",
+            por_segundo(antes),
+            por_segundo(despues),
+            antes.as_secs_f64() / despues.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Este módulo contiene los tests que verifican la deserialización de las
+//! reseñas con el esquema completo del dataset.
+
+#[cfg(test)]
+mod tests {
+    // Imports de crates externas.
+    use csv::{ByteRecord, ReaderBuilder, StringRecord};
+    use std::borrow::Cow;
+
+    // Imports de funciones/estructuras propias.
+    use crate::reporte_ingesta::{MotivoRechazo, Rechazo};
+    use crate::reviews_parseadas::Reseña;
+    use crate::tests_utilidades::en_propiedad;
+
+    const ENCABEZADO: &str = ",app_id,app_name,review_id,language,review,timestamp_created,timestamp_updated,recommended,votes_helpful,votes_funny,weighted_vote_score,comment_count,steam_purchase,received_for_free,written_during_early_access,author.steamid,author.num_games_owned,author.num_reviews,author.playtime_forever,author.playtime_last_two_weeks,author.playtime_at_review,author.last_played";
+
+    const FILA_COMPLETA: &str = "3,292030,The Witcher 3: Wild Hunt,85184605,english,Muy bueno,1611379970,1611379971,True,4,1,0.52,2,True,False,False,76561199054755373,5,3,5587.0,3200.0,5524.0,1611383744.0";
+
+    /// 'Helper' que deserializa una fila con el encabezado completo del dataset.
+    fn deserializar(fila: &str) -> Result<Reseña<'static>, csv::Error> {
+        let datos = format!("{}\n{}\n", ENCABEZADO, fila);
+        let mut lector = ReaderBuilder::new().from_reader(datos.as_bytes());
+        let encabezados = lector.headers().unwrap().clone();
+        let registro: StringRecord = lector.records().next().unwrap().unwrap();
+        registro
+            .deserialize::<Reseña>(Some(&encabezados))
+            .map(en_propiedad)
+    }
+
+    /// 'Test' que verifica que se deserializan todas las columnas con su tipo.
+    #[test]
+    fn test_esquema_completo() {
+        let reseña = deserializar(FILA_COMPLETA).unwrap();
+
+        assert_eq!(reseña.id_juego, Some(292030));
+        assert_eq!(reseña.nombre_juego, "The Witcher 3: Wild Hunt");
+        assert_eq!(reseña.id_reseña, Some(85184605));
+        assert_eq!(reseña.idioma, "english");
+        assert_eq!(reseña.texto, "Muy bueno");
+        assert_eq!(reseña.creada, Some(1611379970));
+        assert_eq!(reseña.actualizada, Some(1611379971));
+        assert_eq!(reseña.recomendada, Some(true));
+        assert_eq!(reseña.votos_utiles, "4");
+        assert_eq!(reseña.votos_graciosos, Some(1));
+        assert_eq!(reseña.puntaje_ponderado, Some(0.52));
+        assert_eq!(reseña.cantidad_comentarios, Some(2));
+        assert_eq!(reseña.compra_en_steam, Some(true));
+        assert_eq!(reseña.recibido_gratis, Some(false));
+        assert_eq!(reseña.acceso_anticipado, Some(false));
+        assert_eq!(reseña.autor_id_steam, Some(76561199054755373));
+        assert_eq!(reseña.autor_cantidad_juegos, Some(5));
+        assert_eq!(reseña.autor_cantidad_reseñas, Some(3));
+        assert_eq!(reseña.autor_minutos_totales, Some(5587.0));
+        assert_eq!(reseña.autor_minutos_ultimas_semanas, Some(3200.0));
+        assert_eq!(reseña.autor_minutos_al_reseñar, Some(5524.0));
+        assert_eq!(reseña.autor_ultima_partida, Some(1611383744));
+    }
+
+    /// 'Test' que verifica que los campos opcionales vacíos se deserializan como `None`.
+    #[test]
+    fn test_campos_opcionales_vacios() {
+        let reseña = deserializar("3,,Juego,,english,Texto,,,,0,,,,,,,,,,,,,").unwrap();
+
+        assert_eq!(reseña.id_juego, None);
+        assert_eq!(reseña.recomendada, None);
+        assert_eq!(reseña.creada, None);
+        assert_eq!(reseña.puntaje_ponderado, None);
+        assert_eq!(reseña.autor_minutos_al_reseñar, None);
+        assert_eq!(reseña.autor_ultima_partida, None);
+    }
+
+    /// 'Test' que verifica que un valor con un tipo inválido en cualquier columna opcional
+    /// hace fallar la deserialización por un campo inválido.
+    #[test]
+    fn test_campos_con_tipo_invalido() {
+        let valida: Vec<&str> = FILA_COMPLETA.split(',').collect();
+        let invalidos = [
+            (1, "uno"),
+            (3, "dos"),
+            (6, "ayer"),
+            (7, "hoy"),
+            (8, "Quizas"),
+            (10, "muchos"),
+            (11, "alto"),
+            (12, "varios"),
+            (13, "Si"),
+            (14, "No"),
+            (15, "Nunca"),
+            (16, "anonimo"),
+            (17, "cien"),
+            (18, "diez"),
+            (19, "mucho"),
+            (20, "algo"),
+            (21, "poco"),
+            (22, "antes"),
+        ];
+
+        for (indice, invalido) in invalidos {
+            let mut fila = valida.clone();
+            fila[indice] = invalido;
+            let rechazo = Rechazo::desde_error_csv(&deserializar(&fila.join(",")).unwrap_err());
+            assert_eq!(rechazo.motivo, MotivoRechazo::CampoInvalido, "{invalido}");
+        }
+    }
+
+    /// 'Test' que verifica que los campos de texto se toman prestados del registro, sin
+    /// copiarlos, aun cuando el texto tiene comillas escapadas.
+    #[test]
+    fn test_campos_de_texto_prestados() {
+        let datos = format!(
+            "{}\n{}\n",
+            ENCABEZADO,
+            "3,1,Juego,2,english,\"Dijo \"\"muy bueno\"\"\",1,1,True,4,0,0.0,0,True,False,False,1,1,1,1.0,1.0,1.0,1.0"
+        );
+        let mut lector = ReaderBuilder::new().from_reader(datos.as_bytes());
+        let encabezados = lector.byte_headers().unwrap().clone();
+        let mut registro = ByteRecord::new();
+        assert!(lector.read_byte_record(&mut registro).unwrap());
+
+        let reseña: Reseña = registro.deserialize(Some(&encabezados)).unwrap();
+        assert_eq!(reseña.texto, "Dijo \"muy bueno\"");
+        for campo in [
+            &reseña.nombre_juego,
+            &reseña.idioma,
+            &reseña.texto,
+            &reseña.votos_utiles,
+        ] {
+            assert!(matches!(campo, Cow::Borrowed(_)));
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Este módulo contiene los tests que verifican que la división de un `.csv`
+//! en bloques respeta los límites de los registros.
+
+#[cfg(test)]
+mod tests {
+    // Imports de crates externas.
+    use csv::{ByteRecord, ReaderBuilder};
+
+    // Imports de funciones/estructuras propias.
+    use crate::segmentador::{Segmentador, TamañoChunk};
+
+    const CSV_CON_SALTOS: &str = "app_name,review,votes_helpful\n\
+        Juego A,\"primera línea\nsegunda, con coma\",3\n\
+        Juego B,\"comillas \"\"escapadas\"\"\n\n\",5\n\
+        Juego C,sin comillas,7";
+
+    /// 'Helper' que parsea todos los registros de una porción de `.csv` sin encabezado.
+    fn parsear_registros(datos: &[u8]) -> Vec<ByteRecord> {
+        ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(datos)
+            .into_byte_records()
+            .map(|registro| registro.unwrap())
+            .collect()
+    }
+
+    /// 'Test' que verifica que, cortando de a un registro por bloque, ningún campo entre
+    /// comillas con saltos de línea queda partido y se obtienen los mismos registros
+    /// que leyendo el archivo completo.
+    #[test]
+    fn test_bloques_respetan_campos_con_saltos_de_linea() {
+        let segmentador = Segmentador::nuevo(CSV_CON_SALTOS.as_bytes(), TamañoChunk::Filas(1))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            segmentador.encabezados(),
+            &ByteRecord::from(vec!["app_name", "review", "votes_helpful"])
+        );
+
+        let bloques: Vec<_> = segmentador.map(|bloque| bloque.unwrap()).collect();
+        assert_eq!(bloques.len(), 3);
+
+        let registros_por_bloque: Vec<ByteRecord> = bloques
+            .iter()
+            .flat_map(|bloque| parsear_registros(&bloque.datos))
+            .collect();
+
+        let mut lector_completo = ReaderBuilder::new()
+            .has_headers(true)
+            .flexible(true)
+            .from_reader(CSV_CON_SALTOS.as_bytes());
+
+        let registros_completos: Vec<ByteRecord> = lector_completo
+            .byte_records()
+            .map(|registro| registro.unwrap())
+            .collect();
+
+        assert_eq!(registros_por_bloque, registros_completos);
+    }
+
+    /// 'Test' que verifica que un archivo con sólo `\r` como fin de línea también se corta
+    /// en bloques del tamaño pedido, con la línea de cada registro, y que se obtienen los
+    /// mismos registros que leyendo el archivo completo.
+    #[test]
+    fn test_bloques_con_retornos_de_carro() {
+        let csv = CSV_CON_SALTOS.replace('\n', "\r");
+        for tamaño in [TamañoChunk::Filas(1), TamañoChunk::Bytes(1)] {
+            let segmentador = Segmentador::nuevo(csv.as_bytes(), tamaño).unwrap().unwrap();
+            assert_eq!(
+                segmentador.encabezados(),
+                &ByteRecord::from(vec!["app_name", "review", "votes_helpful"])
+            );
+
+            let bloques: Vec<_> = segmentador.map(|bloque| bloque.unwrap()).collect();
+            let lineas: Vec<&[u64]> = bloques.iter().map(|bloque| &bloque.lineas[..]).collect();
+            assert_eq!(lineas, [[2], [4], [7]], "{tamaño:?}");
+
+            let registros_por_bloque: Vec<ByteRecord> = bloques
+                .iter()
+                .flat_map(|bloque| parsear_registros(&bloque.datos))
+                .collect();
+            let registros_completos = parsear_registros(csv.as_bytes());
+            assert_eq!(registros_por_bloque, registros_completos[1..]);
+        }
+    }
+
+    /// 'Test' que verifica la línea de cada registro de un bloque con cualquier fin de
+    /// línea, salteando las líneas vacías, y con comillas que abren un campo (al inicio del
+    /// registro o después de una coma) o que están en medio de un campo.
+    #[test]
+    fn test_lineas_de_los_registros() {
+        let csv = format!("{}\n\n\"al inicio\",x\"no abre\n,\"\n\"\n", CSV_CON_SALTOS);
+
+        let lineas = vec![2, 4, 7, 9, 10];
+
+        for fin_de_linea in ["\n", "\r\n", "\r"] {
+            let convertido = csv.replace('\n', fin_de_linea);
+            let bloques: Vec<_> =
+                Segmentador::nuevo(convertido.as_bytes(), TamañoChunk::Filas(100))
+                    .unwrap()
+                    .unwrap()
+                    .map(|bloque| bloque.unwrap())
+                    .collect();
+
+            assert_eq!(bloques.len(), 1);
+            assert_eq!(bloques[0].lineas, lineas, "{fin_de_linea:?}");
+            assert_eq!(
+                parsear_registros(&bloques[0].datos).len(),
+                lineas.len(),
+                "{fin_de_linea:?}"
+            );
+        }
+    }
+
+    /// 'Test' que verifica que un archivo vacío no genera encabezados ni bloques.
+    #[test]
+    fn test_archivo_vacio() {
+        assert!(
+            Segmentador::nuevo("".as_bytes(), TamañoChunk::Filas(10))
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Este módulo contiene los tests del boceto de cuantiles y de la distribución del
+//! tiempo de juego de cada juego.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::boceto_cuantiles::BocetoCuantiles;
+    use crate::estadisticas_serializables::OpcionesSalida;
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::{preparar_salida_final, procesar_archivo_con_pool};
+
+    /// 'Helper' que crea un boceto con los valores indicados.
+    fn boceto(valores: impl IntoIterator<Item = u64>) -> BocetoCuantiles {
+        let mut boceto = BocetoCuantiles::default();
+        for valor in valores {
+            boceto.registrar(valor);
+        }
+        boceto
+    }
+
+    /// 'Test' que verifica que los cuantiles estimados están a menos del 1% de los
+    /// exactos, y que la cantidad, el promedio, el mínimo y el máximo son exactos.
+    #[test]
+    fn test_cuantiles_con_error_relativo_acotado() {
+        // Valores de 1 a 10^6 distribuidos en forma no uniforme, con ceros.
+        let mut valores: Vec<u64> = (0..5000u64).map(|i| (i * i * 7919) % 1_000_000).collect();
+        let boceto = boceto(valores.iter().copied());
+        valores.sort();
+
+        assert_eq!(boceto.cantidad(), valores.len() as u64);
+        let promedio = valores.iter().sum::<u64>() as f64 / valores.len() as f64;
+        assert_eq!(boceto.promedio(), Some(promedio));
+        for q in [0.0, 0.1, 0.25, 0.5, 0.9, 0.99, 1.0] {
+            let exacto = valores[(q * (valores.len() - 1) as f64).floor() as usize] as f64;
+            let estimado = boceto.cuantil(q).unwrap();
+            assert!((estimado - exacto).abs() <= 0.01 * exacto, "{q}");
+        }
+    }
+
+    /// 'Test' que verifica que la fusión de bocetos es exacta: da lo mismo que registrar
+    /// todos los valores en un único boceto, sin importar cómo se repartan.
+    #[test]
+    fn test_fusion_de_bocetos() {
+        let valores: Vec<u64> = (0..1000u64).map(|i| (i * 37) % 250).collect();
+        let completo = boceto(valores.iter().copied());
+
+        for corte in [0, 1, 500, 999, 1000] {
+            let (primeros, ultimos) = valores.split_at(corte);
+            let mut destino = boceto(ultimos.iter().copied());
+            boceto(primeros.iter().copied()).merge_into(&mut destino);
+            assert_eq!(destino, completo);
+        }
+
+        let mut vacio = BocetoCuantiles::default();
+        assert_eq!(vacio.cuantil(0.5), None);
+        assert_eq!(vacio.promedio(), None);
+        completo.clone().merge_into(&mut vacio);
+        assert_eq!(vacio, completo);
+    }
+
+    /// 'Test' que verifica que el tiempo de juego de cada juego de `dataset` cuenta sus
+    /// reseñas, y que las recomendadas y las no recomendadas no superan al total.
+    #[test]
+    fn test_tiempo_de_juego_por_juego() {
+        let opciones = OpcionesProcesamiento::default();
+        let conteo = procesar_archivo_con_pool(&["dataset".to_string()], 4, &opciones);
+        let salida = preparar_salida_final(&conteo, &opciones, &OpcionesSalida::default());
+        assert!(!salida.top_games.is_empty());
+
+        for juego in &salida.top_games {
+            let tiempo = &juego.playtime;
+            assert!(tiempo.all.at_review.count > 0);
+            assert!(
+                tiempo.recommended.at_review.count + tiempo.not_recommended.at_review.count
+                    <= tiempo.all.at_review.count
+            );
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Este módulo contiene los tests que verifican el top-K acotado de las mejores
+//! reseñas.
+
+#[cfg(test)]
+mod tests {
+    // Imports de funciones/estructuras propias.
+    use crate::procesadores::OpcionesProcesamiento;
+    use crate::procesar_archivo_con_pool;
+    use crate::segmentador::TamañoChunk;
+    use crate::top_k::TopK;
+
+    /// 'Test' que verifica que el top guarda sólo los mejores elementos (los menores),
+    /// sin importar el orden en que se insertan.
+    #[test]
+    fn test_guarda_los_mejores() {
+        let valores = [7, 3, 9, 1, 8, 2, 6, 5, 4, 0];
+        for rotacion in 0..valores.len() {
+            let mut top = TopK::nuevo(3);
+            for valor in valores.iter().cycle().skip(rotacion).take(valores.len()) {
+                top.insertar(*valor);
+            }
+
+            assert_eq!(top.ordenados(), vec![&0, &1, &2]);
+        }
+    }
+
+    /// 'Test' que verifica que `admite` indica si un elemento entraría al top.
+    #[test]
+    fn test_admite() {
+        let mut top = TopK::nuevo(2);
+        assert!(top.admite(&10));
+        top.insertar(10);
+        top.insertar(5);
+        assert!(!top.admite(&10));
+        assert!(!top.admite(&11));
+        assert!(top.admite(&9));
+
+        let vacio = TopK::nuevo(0);
+        assert!(!vacio.admite(&0));
+    }
+
+    /// 'Test' que verifica que, al procesar el dataset, se guardan sólo las 10 mejores
+    /// reseñas de cada idioma, y que son las mismas con chunks de distinto tamaño.
+    #[test]
+    fn test_top_de_idioma_acotado() {
+        let mut tops = Vec::new();
+        for filas_por_chunk in [1, 1_000] {
+            let opciones = OpcionesProcesamiento {
+                tamaño_chunk: TamañoChunk::Filas(filas_por_chunk),
+                ..OpcionesProcesamiento::default()
+            };
+
+            let conteo = procesar_archivo_con_pool(&["dataset_test".to_string()], 4, &opciones);
+            let idioma = &conteo.por_idioma["english"];
+            assert!(idioma.cantidad_total > 10);
+            let top: Vec<_> = idioma
+                .top_reviews
+                .ordenados()
+                .into_iter()
+                .cloned()
+                .collect();
+            assert_eq!(top.len(), 10);
+            tops.push(top);
+        }
+
+        assert_eq!(tops[0], tops[1]);
+    }
+}
@@ -0,0 +1,45 @@
+//! Este módulo contiene los 'helpers' compartidos por los módulos de tests.
+
+// Imports de crates externas.
+use std::borrow::Cow;
+
+// Imports de funciones/estructuras propias.
+use crate::calificacion::ConteoRecomendaciones;
+use crate::reviews_parseadas::Reseña;
+
+/// 'Helper' que crea un conteo de recomendaciones.
+pub fn conteo(positivas: u64, negativas: u64) -> ConteoRecomendaciones {
+    ConteoRecomendaciones {
+        positivas,
+        negativas,
+    }
+}
+
+/// 'Helper' que copia los campos de texto prestados de una reseña, independizándola del
+/// registro del que se deserializó (como hacía el parseo a `String`).
+pub fn en_propiedad(reseña: Reseña<'_>) -> Reseña<'static> {
+    Reseña {
+        id_juego: reseña.id_juego,
+        nombre_juego: Cow::Owned(reseña.nombre_juego.into_owned()),
+        id_reseña: reseña.id_reseña,
+        idioma: Cow::Owned(reseña.idioma.into_owned()),
+        texto: Cow::Owned(reseña.texto.into_owned()),
+        creada: reseña.creada,
+        actualizada: reseña.actualizada,
+        recomendada: reseña.recomendada,
+        votos_utiles: Cow::Owned(reseña.votos_utiles.into_owned()),
+        votos_graciosos: reseña.votos_graciosos,
+        puntaje_ponderado: reseña.puntaje_ponderado,
+        cantidad_comentarios: reseña.cantidad_comentarios,
+        compra_en_steam: reseña.compra_en_steam,
+        recibido_gratis: reseña.recibido_gratis,
+        acceso_anticipado: reseña.acceso_anticipado,
+        autor_id_steam: reseña.autor_id_steam,
+        autor_cantidad_juegos: reseña.autor_cantidad_juegos,
+        autor_cantidad_reseñas: reseña.autor_cantidad_reseñas,
+        autor_minutos_totales: reseña.autor_minutos_totales,
+        autor_minutos_ultimas_semanas: reseña.autor_minutos_ultimas_semanas,
+        autor_minutos_al_reseñar: reseña.autor_minutos_al_reseñar,
+        autor_ultima_partida: reseña.autor_ultima_partida,
+    }
+}
@@ -0,0 +1,78 @@
+//! Este módulo contiene la distribución del tiempo de juego de los autores de las
+//! reseñas de cada juego (columnas `author.playtime_at_review` y
+//! `author.playtime_forever`).
+
+// Imports de funciones/estructuras propias.
+use crate::boceto_cuantiles::BocetoCuantiles;
+
+/// Distribución del tiempo de juego, en minutos, de los autores de un grupo de reseñas.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TiempoDeJuego {
+    /// Minutos jugados al escribir la reseña (`author.playtime_at_review`).
+    pub al_reseñar: BocetoCuantiles,
+    /// Minutos jugados en total (`author.playtime_forever`).
+    pub total: BocetoCuantiles,
+}
+
+/// Distribución del tiempo de juego de las reseñas de un juego: de todas y de las que
+/// recomiendan y no recomiendan el juego.
+///
+/// Las reseñas sin valor en `recommended` sólo se cuentan en `todas`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TiempoDeJuegoPorRecomendacion {
+    pub todas: TiempoDeJuego,
+    pub recomendadas: TiempoDeJuego,
+    pub no_recomendadas: TiempoDeJuego,
+}
+
+impl TiempoDeJuego {
+    /// Registra los minutos jugados por el autor de una reseña. Los valores ausentes,
+    /// negativos o no finitos no se cuentan, y el resto se redondea al minuto.
+    fn registrar(&mut self, al_reseñar: Option<f64>, total: Option<f64>) {
+        if let Some(minutos) = a_minutos(al_reseñar) {
+            self.al_reseñar.registrar(minutos);
+        }
+        if let Some(minutos) = a_minutos(total) {
+            self.total.registrar(minutos);
+        }
+    }
+
+    /// Fusiona las distribuciones de `self` en `destino`.
+    fn merge_into(self, destino: &mut TiempoDeJuego) {
+        self.al_reseñar.merge_into(&mut destino.al_reseñar);
+        self.total.merge_into(&mut destino.total);
+    }
+}
+
+impl TiempoDeJuegoPorRecomendacion {
+    /// Registra los minutos jugados por el autor de una reseña, según si recomienda el
+    /// juego (`recommended`).
+    pub fn registrar(
+        &mut self,
+        recomendada: Option<bool>,
+        al_reseñar: Option<f64>,
+        total: Option<f64>,
+    ) {
+        self.todas.registrar(al_reseñar, total);
+        match recomendada {
+            Some(true) => self.recomendadas.registrar(al_reseñar, total),
+            Some(false) => self.no_recomendadas.registrar(al_reseñar, total),
+            None => {}
+        }
+    }
+
+    /// Fusiona las distribuciones de `self` en `destino`.
+    pub fn merge_into(self, destino: &mut TiempoDeJuegoPorRecomendacion) {
+        self.todas.merge_into(&mut destino.todas);
+        self.recomendadas.merge_into(&mut destino.recomendadas);
+        self.no_recomendadas
+            .merge_into(&mut destino.no_recomendadas);
+    }
+}
+
+/// Redondea al minuto un tiempo de juego, descartando los valores negativos o no finitos.
+fn a_minutos(minutos: Option<f64>) -> Option<u64> {
+    minutos
+        .filter(|minutos| minutos.is_finite() && *minutos >= 0.0)
+        .map(|minutos| minutos.round() as u64)
+}
@@ -0,0 +1,69 @@
+//! Este módulo contiene el top-K acotado usado para guardar las mejores reseñas
+//! sin tener que almacenar todas las reseñas procesadas.
+
+// Imports de crates externas.
+use std::collections::BinaryHeap;
+
+/// Top de los `capacidad` mejores elementos vistos, donde un elemento es mejor que otro
+/// si es menor según su `Ord`.
+///
+/// Se implementa con un heap cuya cima es el peor de los elementos guardados, por lo que
+/// decidir si un elemento nuevo entra al top cuesta O(1), y reemplazar al peor, O(log K).
+/// Así, la memoria de un top no depende de cuántos elementos se registran en él.
+#[derive(Debug, Clone)]
+pub struct TopK<T: Ord> {
+    capacidad: usize,
+    elementos: BinaryHeap<T>,
+}
+
+impl<T: Ord> TopK<T> {
+    /// Crea un top vacío que guarda, como máximo, `capacidad` elementos (con `usize::MAX`,
+    /// guarda todos).
+    pub fn nuevo(capacidad: usize) -> Self {
+        TopK {
+            capacidad,
+            elementos: BinaryHeap::new(),
+        }
+    }
+
+    /// Indica si `elemento` entraría al top, sin registrarlo.
+    ///
+    /// Sirve para evitar construir (o clonar) elementos que serían descartados: el
+    /// elemento puede ser de otro tipo, siempre que se pueda comparar con los del top.
+    pub fn admite<U: PartialOrd<T>>(&self, elemento: &U) -> bool {
+        if self.elementos.len() < self.capacidad {
+            return true;
+        }
+
+        self.elementos.peek().is_some_and(|peor| elemento < peor)
+    }
+
+    /// Registra un elemento, descartándolo si no entra al top o desplazando al peor.
+    pub fn insertar(&mut self, elemento: T) {
+        if self.elementos.len() < self.capacidad {
+            self.elementos.push(elemento);
+            return;
+        }
+
+        // Al soltar `peor`, el heap se reordena con el elemento que lo reemplazó.
+        if let Some(mut peor) = self.elementos.peek_mut()
+            && elemento < *peor
+        {
+            *peor = elemento;
+        }
+    }
+
+    /// Fusiona los elementos de `self` en `destino`, moviéndolos en lugar de clonarlos.
+    pub fn merge_into(self, destino: &mut TopK<T>) {
+        for elemento in self.elementos {
+            destino.insertar(elemento);
+        }
+    }
+
+    /// Elementos del top, del mejor al peor.
+    pub fn ordenados(&self) -> Vec<&T> {
+        let mut elementos: Vec<_> = self.elementos.iter().collect();
+        elementos.sort();
+        elementos
+    }
+}